@@ -0,0 +1,27 @@
+/*
+    Times every day against its full input, run with `cargo bench`.
+    Days can be picked by number, e.g. `cargo bench -- 10 14`
+*/
+use advent_of_code_2023::inputs::{read_input, PUZZLE_INPUT};
+use advent_of_code_2023::solution::{Day, DAYS};
+use advent_of_code_2023::timing::{time_day, timing_table};
+
+const RUNS: usize = 5;
+
+fn main() {
+    // cargo passes --bench along, anything that is a number is a day to run
+    let picked: Vec<usize> = std::env::args()
+        .skip(1)
+        .filter_map(|arg| arg.parse().ok())
+        .collect();
+    let days = DAYS
+        .iter()
+        .filter(|day| picked.is_empty() || picked.contains(&day.number));
+    let timings: Vec<_> = days
+        .map(|day: &Day| {
+            let input = read_input(day.number, PUZZLE_INPUT).unwrap();
+            time_day(day, &input, RUNS).unwrap()
+        })
+        .collect();
+    print!("{}", timing_table(&timings));
+}
@@ -0,0 +1,159 @@
+use crate::error::{AocError, ErrorKind};
+use crate::inputs::InputStore;
+use crate::solution::get_day;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::panic;
+
+/*
+    One line of the answers manifest: solving the part of the day against the input variant
+    has to give the answer. An answer of '?' means it isn't known yet
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedAnswer {
+    pub day: usize,
+    pub part: usize,
+    pub variant: String,
+    pub answer: Option<String>,
+}
+
+impl ExpectedAnswer {
+    fn parse_line(line: &str) -> Result<ExpectedAnswer, AocError> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let [day, part, variant, answer] = tokens[..] else {
+            return Err(AocError::new(ErrorKind::WrongTokenCount {
+                what: "answer entry",
+                expected: 4,
+                found: tokens.len(),
+            }));
+        };
+        let number = |what, token: &str| {
+            token.parse().map_err(|_| {
+                AocError::new(ErrorKind::InvalidNumber {
+                    what,
+                    token: token.to_string(),
+                })
+            })
+        };
+        Ok(ExpectedAnswer {
+            day: number("day", day)?,
+            part: number("part", part)?,
+            variant: variant.to_string(),
+            answer: (answer != "?").then(|| answer.to_string()),
+        })
+    }
+}
+
+/*
+    Reads the manifest, one "<day> <part> <input variant> <answer>" entry per line.
+    Blank lines and lines starting with '#' are skipped
+*/
+pub fn parse_manifest(s: &str) -> Result<Vec<ExpectedAnswer>, AocError> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(line_n, line)| ExpectedAnswer::parse_line(line).map_err(|e| e.at_line(line_n + 1)))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail { found: String },
+    Missing { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub expected: ExpectedAnswer,
+    pub outcome: Outcome,
+}
+
+/*
+    Solves a manifest entry against the given input and compares it to the expected answer.
+    Errors and panics while solving count as failures, while entries that can't be
+    checked (no answer yet, an unknown day or a part that isn't implemented) are missing
+*/
+pub fn check_answer(expected: &ExpectedAnswer, input: &str) -> Check {
+    let missing = |reason: String| Check {
+        expected: expected.clone(),
+        outcome: Outcome::Missing { reason },
+    };
+    let Some(day) = get_day(expected.day) else {
+        return missing("the day is not implemented".to_string());
+    };
+    let solved = panic::catch_unwind(|| {
+        day.solve(expected.part, input)
+            .map(|answer| answer.to_string())
+    });
+    let found = match solved {
+        Ok(Ok(found)) => found,
+        Ok(Err(AocError {
+            kind: ErrorKind::NotImplemented { .. },
+            ..
+        })) => return missing("the part is not implemented".to_string()),
+        Ok(Err(e)) => format!("error: {}", e),
+        Err(_) => "a panic".to_string(),
+    };
+    let outcome = match &expected.answer {
+        None => Outcome::Missing {
+            reason: format!("no expected answer, got {}", found),
+        },
+        Some(answer) if *answer == found => Outcome::Pass,
+        Some(_) => Outcome::Fail { found },
+    };
+    Check {
+        expected: expected.clone(),
+        outcome,
+    }
+}
+
+// Same as check_answer, reading the input variant of the entry from the store
+pub fn check_in(expected: &ExpectedAnswer, store: &InputStore) -> Check {
+    match store.read(expected.day, &expected.variant) {
+        Ok(input) => check_answer(expected, &input),
+        Err(e) => Check {
+            expected: expected.clone(),
+            outcome: Outcome::Missing {
+                reason: e.kind.to_string(),
+            },
+        },
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let label = match self.outcome {
+            Outcome::Pass => "PASS",
+            Outcome::Fail { .. } => "FAIL",
+            Outcome::Missing { .. } => "MISSING",
+        };
+        write!(
+            f,
+            "{:<7} day {} part {} ({})",
+            label, self.expected.day, self.expected.part, self.expected.variant
+        )?;
+        match &self.outcome {
+            Outcome::Pass => Ok(()),
+            Outcome::Fail { found } => write!(
+                f,
+                ": expected {}, got {}",
+                self.expected.answer.as_deref().unwrap_or("?"),
+                found
+            ),
+            Outcome::Missing { reason } => write!(f, ": {}", reason),
+        }
+    }
+}
+
+// Counts the outcomes, e.g. "30 passed, 1 failed, 5 missing"
+pub fn summary(checks: &[Check]) -> String {
+    let count =
+        |label: fn(&Outcome) -> bool| checks.iter().filter(|check| label(&check.outcome)).count();
+    format!(
+        "{} passed, {} failed, {} missing",
+        count(|outcome| matches!(outcome, Outcome::Pass)),
+        count(|outcome| matches!(outcome, Outcome::Fail { .. })),
+        count(|outcome| matches!(outcome, Outcome::Missing { .. })),
+    )
+}
@@ -1,9 +1,14 @@
 use crate::day_10_pipe_maze::Pipe::{NorthEast, NorthWest, SouthEast, SouthWest};
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
-use std::fmt::{Display, Formatter, Write};
-use std::ops::Index;
+use log::{debug, trace};
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
+const DAY: usize = 10;
+
 // const SYMBOLS: [char; 6] = ['═', '║', '╔', '╗', '╚', '╝'];
 pub fn ugly_pipe_maze_to_cute_pipe_maze(s: &str) -> String {
     s.trim()
@@ -125,23 +130,24 @@ impl Pipe {
     }
 }
 pub struct PipeMaze {
-    grid: Vec<Vec<Pipe>>,
+    grid: Grid<Pipe>,
     pub start: Option<(usize, usize)>,
-    width: usize,
-    height: usize,
 }
 
 impl PipeMaze {
-    pub fn is_inside_loop(&self, row_n: usize, col_n: usize) -> bool {
-        let pipes_in_loop: Vec<(usize, usize, &Pipe)> = self
-            .get_all_points_in_loop()
-            .unwrap()
-            .into_iter()
+    pub fn is_inside_loop(&self, row_n: usize, col_n: usize) -> Result<bool, AocError> {
+        let loop_points = self.get_all_points_in_loop()?;
+        Ok(self.is_inside(&loop_points, row_n, col_n))
+    }
+
+    fn is_inside(&self, loop_points: &[[usize; 2]], row_n: usize, col_n: usize) -> bool {
+        let pipes_in_loop: Vec<(usize, usize, &Pipe)> = loop_points
+            .iter()
+            .copied()
             .filter(|[row_p, col_p]| row_p == &row_n && col_p < &col_n)
-            .filter(|[row_p, col_p]| self.grid[*row_p][*col_p] != Pipe::EastWest)
-            .map(|[row_p, col_p]| (row_p, col_p, &self.grid[row_p][col_p]))
+            .filter(|[row_p, col_p]| self.grid[(*row_p, *col_p)] != Pipe::EastWest)
+            .map(|[row_p, col_p]| (row_p, col_p, &self.grid[(row_p, col_p)]))
             .collect_vec(); // Remove horizontal pipes
-                            // println!("Points in same row, {:?}", pipes_in_loop);
         let mut edge_count = 0;
         let mut last_pipe = &Pipe::Blocked;
         for (_, _, pipe) in pipes_in_loop {
@@ -155,22 +161,22 @@ impl PipeMaze {
             };
             last_pipe = pipe;
         }
-        // println!("edges {}", edge_count);
         edge_count % 2 == 1
     }
-    pub fn count_area_inside_loop(&self) -> usize {
-        let loop_points = self.get_all_points_in_loop().unwrap();
-        (0..self.height)
-            .cartesian_product(0..self.width)
+    pub fn count_area_inside_loop(&self) -> Result<usize, AocError> {
+        let loop_points = self.get_all_points_in_loop()?;
+        Ok(self
+            .grid
+            .positions()
             .filter(|(row_n, col_n)| !loop_points.contains(&[*row_n, *col_n]))
             .map(|(row_n, col_n)| {
-                println!("{},", self.grid[row_n][col_n]);
+                trace!("({}, {}) {}", row_n, col_n, self.grid[(row_n, col_n)]);
                 (row_n, col_n)
             })
-            .filter(|(row_n, col_n)| self.is_inside_loop(*row_n, *col_n))
-            .count()
+            .filter(|(row_n, col_n)| self.is_inside(&loop_points, *row_n, *col_n))
+            .count())
     }
-    pub fn farthest_point_distance(&self) -> Result<usize, String> {
+    pub fn farthest_point_distance(&self) -> Result<usize, AocError> {
         let loop_length = self.loop_length()?;
         if loop_length % 2 == 0 {
             Ok(loop_length / 2)
@@ -178,10 +184,10 @@ impl PipeMaze {
             Ok((loop_length / 2) + 1)
         }
     }
-    pub fn get_all_points_in_loop(&self) -> Result<Vec<[usize; 2]>, String> {
+    pub fn get_all_points_in_loop(&self) -> Result<Vec<[usize; 2]>, AocError> {
         let (start_row, start_col) = self
             .start
-            .expect("Has to be, otherwise why are you calling this");
+            .ok_or(AocError::invalid(DAY, "The maze has no start"))?;
         // Assumes there IS a loop in start
         // We can start at the first, or second direction of self, lets do first
         let last_pipe = self
@@ -190,21 +196,28 @@ impl PipeMaze {
         // Start from whichever
         let mut approach_direction: Dir = last_pipe
             .to_dirs()
-            .ok_or("The given start point is not a pipe with a direction".to_string())?
+            .ok_or(AocError::invalid(
+                DAY,
+                "The given start point is not a pipe with a direction",
+            ))?
             .0;
 
         let mut last_row = start_row;
         let mut last_col = start_col;
         let mut points_in_loop = vec![[last_row, last_col]];
         loop {
-            // println!("dir={:?}", approach_direction);
             (last_row, last_col, approach_direction) = self
                 .next(last_row, last_col, &approach_direction)
                 .ok_or_else(|| {
-                    format!(
-                        "The pipe (row={}, col={}) does not have a valid successor when approaching from {:?}",last_row, last_col,
-                         approach_direction
+                    AocError::invalid(
+                        DAY,
+                        format!(
+                            "The pipe does not have a valid successor when approaching from {:?}",
+                            approach_direction
+                        ),
                     )
+                    .at_line(last_row + 1)
+                    .at_column(last_col + 1)
                 })?;
 
             if last_row == start_row && last_col == start_col {
@@ -217,7 +230,7 @@ impl PipeMaze {
             points_in_loop.push([last_row, last_col]);
         }
     }
-    pub fn loop_length(&self) -> Result<usize, String> {
+    pub fn loop_length(&self) -> Result<usize, AocError> {
         let all_points_in_loop = self.get_all_points_in_loop()?;
         Ok(all_points_in_loop.iter().count() - 1)
     }
@@ -225,9 +238,9 @@ impl PipeMaze {
     fn next(&self, row_n: usize, col_n: usize, comes_from: &Dir) -> Option<(usize, usize, Dir)> {
         let current = self.get(row_n, col_n)?;
         let next_dir = current.next(comes_from)?;
-        let next_coordinates_offset = next_dir.to_coordinate_offset();
-        let next_row_n = row_n.checked_add_signed(next_coordinates_offset.0)?;
-        let next_col_n = col_n.checked_add_signed(next_coordinates_offset.1)?;
+        let (next_row_n, next_col_n) = self
+            .grid
+            .offset((row_n, col_n), next_dir.to_coordinate_offset())?;
         let approaching_next_from_from = next_dir.complement();
 
         // row_n, col_n, current, comes_from,next_dir,next_row_n, next_col_n, approaching_next_from_from );
@@ -235,27 +248,23 @@ impl PipeMaze {
     }
 
     fn get(&self, row_n: usize, col_n: usize) -> Option<&Pipe> {
-        let row_n: usize = row_n.try_into().ok()?;
-        let col_n: usize = col_n.try_into().ok()?;
-        self.grid.get(row_n)?.get(col_n)
+        self.grid.get(row_n, col_n)
     }
 
     fn infer_pipe(&self, row_n: usize, col_n: usize) -> Option<Pipe> {
         let mut connected_directions = vec![];
         for dir in Dir::iter() {
-            let (row_offset, col_offset) = dir.to_coordinate_offset();
-            let next_row = row_n.checked_add_signed(row_offset);
-            let next_column = col_n.checked_add_signed(col_offset);
-
-            if let (Some(next_row), Some(next_column)) = (next_row, next_column) {
+            let next = self.grid.offset((row_n, col_n), dir.to_coordinate_offset());
+            if let Some((next_row, next_column)) = next {
                 let complement = dir.complement();
-                if let Some(pipe) = self.get(next_row, next_column) {
-                    // If we approach the next pipe from our north, we are approaching it from its south
-                    // let see if its actually connected
-                    if let Some(_) = pipe.next(&complement) {
-                        // Yes! it is actually connected in the given direction (dir)
-                        connected_directions.push(dir);
-                    }
+                // If we approach the next pipe from our north, we are approaching it from its south
+                // let see if its actually connected
+                if self.grid[(next_row, next_column)]
+                    .next(&complement)
+                    .is_some()
+                {
+                    // Yes! it is actually connected in the given direction (dir)
+                    connected_directions.push(dir);
                 }
             }
         }
@@ -264,7 +273,7 @@ impl PipeMaze {
                 <[Dir; 2]>::try_from(connected_directions).unwrap(),
             ))
         } else {
-            println!(
+            debug!(
                 "Impossible to infer pipe since connections not equal 2, but {}",
                 connected_directions.len()
             );
@@ -276,7 +285,7 @@ impl Display for PipeMaze {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "PipeMaze(")?;
         if let Some((row_n, col_n)) = self.start {
-            let pipetype_of_start = self.grid.index(row_n).index(col_n);
+            let pipetype_of_start = &self.grid[(row_n, col_n)];
             write!(
                 f,
                 "starts=(row={}, col={}, pipe={}),",
@@ -287,56 +296,55 @@ impl Display for PipeMaze {
         }
 
         write!(f, "grid=(\n",)?;
-
-        for row in self.grid.iter() {
-            for pipe in row.iter() {
-                f.write_str(&*pipe.to_string())?;
-            }
-            f.write_char('\n')?;
-        }
+        write!(f, "{}", self.grid)?;
         f.write_str("))")?;
         Ok(())
         //
     }
 }
 impl FromStr for PipeMaze {
-    type Err = String;
+    type Err = AocError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let cute_pipe_maze: String = ugly_pipe_maze_to_cute_pipe_maze(s);
-        let mut start = None;
-        let mut grid: Vec<Vec<Pipe>> = vec![];
-        for (row_n, line) in cute_pipe_maze.trim().lines().enumerate() {
-            let mut row = vec![];
-            for (col_n, c) in line.trim().chars().enumerate() {
-                if c == 'S' {
-                    start = Some((row_n, col_n));
-                }
-                let as_pipe = Pipe::from_char(c);
-                row.push(as_pipe);
-            }
-            grid.push(row);
-        }
+        let chars = Grid::parse(&cute_pipe_maze, "maze", Some).map_err(|e| e.in_day(DAY))?;
 
-        if !grid.iter().map(|v| v.len()).all_equal() {
-            return Err("Not all the rows have the same amount of characters!!".to_string());
+        let mut starts = chars.iter().filter(|(_, &c)| c == 'S').map(|(at, _)| at);
+        let start = starts.next();
+        if let Some((row_n, col_n)) = starts.next() {
+            return Err(AocError::invalid(DAY, "The maze has more than one start")
+                .at_line(row_n + 1)
+                .at_column(col_n + 1));
         }
-        let height = grid.len();
-        let width = grid[0].len();
         let mut pipe_maze = PipeMaze {
-            grid,
+            grid: chars.map(|&c| Pipe::from_char(c)),
             start,
-            height,
-            width,
         };
 
         match start {
             None => Ok(pipe_maze),
             Some((row_n, col_n)) => {
-                let inferred = pipe_maze.infer_pipe(row_n, col_n).expect("Inputs should only have one S, and it should only be inferrable to be a single kind");
-                pipe_maze.start = Some((row_n, col_n));
-                pipe_maze.grid[row_n][col_n] = inferred;
+                let inferred = pipe_maze.infer_pipe(row_n, col_n).ok_or(
+                    AocError::invalid(DAY, "The start is not connected to exactly two pipes")
+                        .at_line(row_n + 1)
+                        .at_column(col_n + 1),
+                )?;
+                pipe_maze.grid[(row_n, col_n)] = inferred;
                 Ok(pipe_maze)
             }
         }
     }
 }
+
+impl Solution for PipeMaze {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.farthest_point_distance()?))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.count_area_inside_loop()?))
+    }
+}
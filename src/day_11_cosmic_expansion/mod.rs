@@ -1,8 +1,14 @@
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 use std::cmp::{max, min};
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
+
+const DAY: usize = 11;
+
 #[derive(Clone, PartialEq)]
 pub enum Observation {
     EmptySpace,
@@ -32,86 +38,45 @@ pub fn taxicab_distance(a: (usize, usize), b: (usize, usize)) -> usize {
     x_distance + y_distance
 }
 pub struct SpaceImage {
-    pub grid: Vec<Vec<Observation>>,
+    pub grid: Grid<Observation>,
 }
 
 impl FromStr for SpaceImage {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid = s
-            .trim()
-            .lines()
-            .map(|line| {
-                line.trim()
-                    .chars()
-                    .map(|c| Observation::from_char(c))
-                    .collect::<Option<Vec<Observation>>>()
-            })
-            .collect::<Option<Vec<Vec<Observation>>>>()
-            .ok_or("Unable to parse rows and columns from the given string".to_string())?;
+        let grid = Grid::parse(s, "image", Observation::from_char).map_err(|e| e.in_day(DAY))?;
         Ok(SpaceImage { grid })
     }
 }
 
 impl Display for SpaceImage {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in &self.grid {
-            for obs in row {
-                write!(f, "{}", obs.to_char())?;
-            }
-            write!(f, "\n")?;
-        }
-        Ok(())
+        f.write_str(&self.grid.render(Observation::to_char))
     }
 }
 impl SpaceImage {
     pub fn age_by(self, age: usize) -> SpaceImage {
-        // Expands the spaceimage
-        let mut grid_with_repeated_rows: Vec<Vec<Observation>> = vec![];
-
-        // Check all rows
-        for row in &self.grid {
-            if row.iter().all(|ob| ob == &Observation::EmptySpace) {
-                for _ in 0..age {
-                    grid_with_repeated_rows.push(row.clone());
-                }
-            }
-            grid_with_repeated_rows.push(row.clone());
-        }
-
-        // Check if there are columns that need to be repeated
-        let n_columns = &self.grid[0].len();
-        let columns_to_repeat = (0..*n_columns)
-            .filter(|col_n| {
-                self.grid
-                    .iter()
-                    .all(|row| row[*col_n] == Observation::EmptySpace)
-            })
-            .collect::<HashSet<usize>>();
-
-        let mut grid_with_repeated_cols: Vec<Vec<Observation>> = vec![];
-
-        for row in grid_with_repeated_rows {
-            let mut new_row: Vec<Observation> = vec![];
-            for (i, obs) in row.iter().enumerate() {
-                if columns_to_repeat.contains(&i) {
-                    for _ in 0..age {
-                        new_row.push(obs.clone());
-                    }
-                }
-                new_row.push(obs.clone());
-            }
-            grid_with_repeated_cols.push(new_row);
-        }
-        SpaceImage {
-            grid: grid_with_repeated_cols,
-        }
+        // Expands the spaceimage, every empty row and column is repeated age more times
+        let repeated = |empty: Vec<usize>, length: usize| {
+            (0..length)
+                .flat_map(|n| {
+                    let times = if empty.contains(&n) { age + 1 } else { 1 };
+                    std::iter::repeat_n(n, times)
+                })
+                .collect_vec()
+        };
+        let rows = repeated(self.find_empty_rows(), self.grid.height());
+        let cols = repeated(self.find_empty_cols(), self.grid.width());
+        let grid = Grid::from_fn(rows.len(), cols.len(), |row_n, col_n| {
+            self.grid[(rows[row_n], cols[col_n])].clone()
+        });
+        SpaceImage { grid }
     }
 
     pub fn find_empty_rows(&self) -> Vec<usize> {
         self.grid
-            .iter()
+            .rows()
             .enumerate()
             .filter(|(_, row)| row.iter().all(|ob| ob == &Observation::EmptySpace))
             .map(|(row_n, _)| row_n)
@@ -119,25 +84,22 @@ impl SpaceImage {
     }
 
     pub fn find_empty_cols(&self) -> Vec<usize> {
-        (0..self.grid[0].len())
-            .filter(|col_n| {
-                self.grid
-                    .iter()
-                    .all(|row| row[*col_n] == Observation::EmptySpace)
+        self.grid
+            .columns()
+            .enumerate()
+            .filter_map(|(col_n, mut column)| {
+                column
+                    .all(|ob| ob == &Observation::EmptySpace)
+                    .then_some(col_n)
             })
             .collect_vec()
     }
     pub fn find_galaxies(&self) -> HashSet<(usize, usize)> {
-        let mut locations: HashSet<(usize, usize)> = HashSet::new();
-
-        for (row_n, row) in self.grid.iter().enumerate() {
-            for (col_n, obs) in row.iter().enumerate() {
-                if obs == &Observation::Galaxy {
-                    locations.insert((row_n, col_n));
-                }
-            }
-        }
-        locations
+        self.grid
+            .iter()
+            .filter(|(_, obs)| obs == &&Observation::Galaxy)
+            .map(|(location, _)| location)
+            .collect()
     }
 
     pub fn find_smallest_distances(&self, grown_times: usize) -> Vec<usize> {
@@ -171,3 +133,21 @@ impl SpaceImage {
         distances
     }
 }
+
+impl Solution for SpaceImage {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(
+            self.find_smallest_distances(2).iter().sum::<usize>(),
+        ))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(
+            self.find_smallest_distances(1000000).iter().sum::<usize>(),
+        ))
+    }
+}
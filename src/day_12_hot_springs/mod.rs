@@ -1,7 +1,12 @@
+use crate::error::{AocError, ErrorKind};
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
+use log::debug;
 use std::collections::HashMap;
 use std::ops::Add;
 
+const DAY: usize = 12;
+
 #[derive(Eq, PartialEq, Hash)]
 struct StringAndVectorKey {
     the_string: String,
@@ -36,27 +41,45 @@ impl HotSpringsMemo {
         &mut self,
         the_row: &str,
         unfold_n: usize,
-    ) -> Result<usize, String> {
-        let (condition_record, count_tokens) = the_row
-            .trim()
-            .split_once(" ")
-            .ok_or("Unable to parse row".to_string())?;
-
-        if !condition_record.chars().all(|c| {
-            c == HotSpringsMemo::UNKNOWN
-                || c == HotSpringsMemo::OPERATIONAL
-                || c == HotSpringsMemo::DAMAGED
+    ) -> Result<usize, AocError> {
+        let (condition_record, count_tokens) =
+            the_row.trim().split_once(" ").ok_or(AocError::day(
+                DAY,
+                ErrorKind::MissingDelimiter {
+                    what: "row",
+                    delimiter: " ",
+                },
+            ))?;
+
+        if let Some((col_n, c)) = condition_record.chars().enumerate().find(|(_, c)| {
+            *c != HotSpringsMemo::UNKNOWN
+                && *c != HotSpringsMemo::OPERATIONAL
+                && *c != HotSpringsMemo::DAMAGED
         }) {
-            return Err(
-                "Not all the chars in a condition record were correct (. # ?)!".to_string(),
-            );
+            return Err(AocError::day(
+                DAY,
+                ErrorKind::InvalidChar {
+                    what: "condition record",
+                    character: c,
+                },
+            )
+            .at_column(col_n + 1));
         }
 
         let counts: Vec<usize> = count_tokens
             .split(",")
-            .map(|token| token.parse())
-            .collect::<Result<Vec<usize>, _>>()
-            .map_err(|_| "Unable to parse all number tokens from a row")?;
+            .map(|token| {
+                token.trim().parse().map_err(|_| {
+                    AocError::day(
+                        DAY,
+                        ErrorKind::InvalidNumber {
+                            what: "group size",
+                            token: token.to_string(),
+                        },
+                    )
+                })
+            })
+            .collect::<Result<Vec<usize>, AocError>>()?;
         let record_array = condition_record.chars().collect_vec();
 
         let mut unfolded_record_array = record_array.clone();
@@ -78,13 +101,17 @@ impl HotSpringsMemo {
         &mut self,
         the_paragraph: &str,
         unfold_n: usize,
-    ) -> Result<usize, String> {
+    ) -> Result<usize, AocError> {
         let result = the_paragraph
-            .trim()
             .lines()
-            .map(|the_row| self.get_arrangements_for_row(the_row, unfold_n))
+            .enumerate()
+            .filter(|(_, the_row)| !the_row.trim().is_empty())
+            .map(|(row_n, the_row)| {
+                self.get_arrangements_for_row(the_row, unfold_n)
+                    .map_err(|e| e.at_line(row_n + 1))
+            })
             .fold_ok(0, Add::add);
-        println!("Cache size: {}", self.memory.len());
+        debug!("Cache size: {}", self.memory.len());
         result
     }
 
@@ -148,3 +175,21 @@ impl HotSpringsMemo {
         return matches;
     }
 }
+
+pub struct SpringRecords(String);
+
+impl Solution for SpringRecords {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(SpringRecords(input.to_string()))
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        let mut memo = HotSpringsMemo::new();
+        Ok(Box::new(memo.get_arrangements_for_paragraph(&self.0, 1)?))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        let mut memo = HotSpringsMemo::new();
+        Ok(Box::new(memo.get_arrangements_for_paragraph(&self.0, 5)?))
+    }
+}
@@ -1,36 +1,42 @@
 use crate::day_13_point_of_incidence::Observation::{Ash, Rock};
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 use std::str::FromStr;
 
+const DAY: usize = 13;
+
 #[derive(Eq, PartialEq, Clone)]
 enum Observation {
     Ash,
     Rock,
 }
 impl Observation {
-    fn from_char(c: char) -> Result<Observation, String> {
+    fn from_char(c: char) -> Option<Observation> {
         match c {
-            '#' => Ok(Ash),
-            '.' => Ok(Rock),
-            _ => Err(format!("Unable to parse char to Observation ({:?})", c).to_string()),
+            '#' => Some(Ash),
+            '.' => Some(Rock),
+            _ => None,
         }
     }
 }
 struct Cluster {
-    elements: Vec<Vec<Observation>>,
+    elements: Grid<Observation>,
 }
 fn is_reflection_row(
-    elements: &Vec<Vec<Observation>>,
+    elements: &Grid<Observation>,
     between: usize,
     number_of_smudges: usize,
 ) -> bool {
-    if between == 0 || between == elements.len() {
+    if between == 0 || between == elements.height() {
         return false;
     }
     // At this point we know we are in inbetweens
     // Gotta save a list of up and down
-    let up = &elements[0..between];
-    let down = &elements[between..];
+    let rows = elements.rows().collect_vec();
+    let up = &rows[0..between];
+    let down = &rows[between..];
 
     // Gotta reverse up, and compare row by row
     let up_rev = up.iter().rev();
@@ -39,7 +45,7 @@ fn is_reflection_row(
     up_rev.zip(down).map(count_differences).sum::<usize>() == number_of_smudges
 }
 
-fn count_differences<A>((v1, v2): (&Vec<A>, &Vec<A>)) -> usize
+fn count_differences<A>((v1, v2): (&&[A], &&[A])) -> usize
 where
     A: PartialEq,
 {
@@ -47,35 +53,17 @@ where
 }
 impl Cluster {
     fn find_horizontal_reflection_row(&self, number_of_smudges: usize) -> Option<usize> {
-        for i in 0..self.elements.len() {
-            if is_reflection_row(&self.elements, i, number_of_smudges) {
-                return Some(i);
-            }
-        }
-        None
+        (0..self.elements.height())
+            .find(|&i| is_reflection_row(&self.elements, i, number_of_smudges))
     }
     fn find_vertical_reflection_col(&self, number_of_smudges: usize) -> Option<usize> {
         // Gotta rotate
-        let mut rotated = vec![];
-        for i in 0..self.elements[0].len() {
-            let row = self
-                .elements
-                .iter()
-                .map(|row| &row[i])
-                .cloned()
-                .collect_vec();
-            rotated.push(row);
-        }
+        let rotated = self.elements.transposed();
 
-        for i in 0..self.elements[0].len() {
-            if is_reflection_row(&rotated, i, number_of_smudges) {
-                return Some(i);
-            }
-        }
-        None
+        (0..rotated.height()).find(|&i| is_reflection_row(&rotated, i, number_of_smudges))
     }
 
-    fn summarize_pattern(self, number_of_smudges: usize) -> usize {
+    fn summarize_pattern(&self, number_of_smudges: usize) -> usize {
         let reflection_row = self
             .find_horizontal_reflection_row(number_of_smudges)
             .unwrap_or(0)
@@ -87,27 +75,67 @@ impl Cluster {
     }
 }
 
-pub fn summarize_reflections(buffer: &str, number_of_smudges: usize) -> usize {
-    let all_clusters = buffer.trim().split("\n\n");
-    let mut sum = 0;
-    for cluster_str in all_clusters {
-        let cluster: Cluster = cluster_str.parse().unwrap();
-        sum += cluster.summarize_pattern(number_of_smudges);
+pub struct MirrorNotes {
+    clusters: Vec<Cluster>,
+}
+
+impl MirrorNotes {
+    pub fn summarize(&self, number_of_smudges: usize) -> usize {
+        self.clusters
+            .iter()
+            .map(|cluster| cluster.summarize_pattern(number_of_smudges))
+            .sum()
     }
-    sum
 }
-impl FromStr for Cluster {
-    type Err = String;
+
+impl FromStr for MirrorNotes {
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut elements = vec![];
-        for line in s.trim().lines() {
-            let row = line
-                .chars()
-                .map(|c| Observation::from_char(c))
-                .collect::<Result<Vec<Observation>, String>>()?;
-            elements.push(row);
+        // Clusters are separated by blank lines, keep track of where each one starts
+        let mut clusters = vec![];
+        let mut cluster_start = 0;
+        let lines = s.lines().collect_vec();
+        for (line_n, line) in lines.iter().enumerate().chain([(lines.len(), &"")]) {
+            if line.trim().is_empty() {
+                if line_n > cluster_start {
+                    let block = lines[cluster_start..line_n].join("\n");
+                    let cluster = block
+                        .parse::<Cluster>()
+                        .map_err(|e| e.offset_line(cluster_start))?;
+                    clusters.push(cluster);
+                }
+                cluster_start = line_n + 1;
+            }
         }
+        Ok(MirrorNotes { clusters })
+    }
+}
+
+pub fn summarize_reflections(buffer: &str, number_of_smudges: usize) -> Result<usize, AocError> {
+    let notes: MirrorNotes = buffer.parse()?;
+    Ok(notes.summarize(number_of_smudges))
+}
+impl FromStr for Cluster {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let elements =
+            Grid::parse(s, "pattern", Observation::from_char).map_err(|e| e.in_day(DAY))?;
         Ok(Self { elements })
     }
 }
+
+impl Solution for MirrorNotes {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.summarize(0)))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.summarize(1)))
+    }
+}
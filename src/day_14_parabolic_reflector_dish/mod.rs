@@ -1,7 +1,12 @@
 use crate::day_14_parabolic_reflector_dish::Observation::{CubeRock, Empty, RoundRock};
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 use std::fmt::{Display, Formatter, Write};
 use std::str::FromStr;
 
+const DAY: usize = 14;
+
 #[derive(Clone, PartialEq)]
 enum Observation {
     RoundRock,
@@ -10,12 +15,12 @@ enum Observation {
 }
 
 impl Observation {
-    fn from_char(c: char) -> Result<Observation, &'static str> {
+    fn from_char(c: char) -> Option<Observation> {
         match c {
-            'O' => Ok(RoundRock),
-            '#' => Ok(CubeRock),
-            '.' => Ok(Empty),
-            _ => Err("Unable to parse!"),
+            'O' => Some(RoundRock),
+            '#' => Some(CubeRock),
+            '.' => Some(Empty),
+            _ => None,
         }
     }
 
@@ -27,18 +32,14 @@ impl Observation {
         }
     }
 }
+#[derive(Clone)]
 pub struct Platform {
-    elements: Vec<Vec<Observation>>,
+    elements: Grid<Observation>,
 }
 
 impl Display for Platform {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in &self.elements {
-            for element in row {
-                f.write_char(element.to_char())?;
-            }
-            f.write_char('\n')?;
-        }
+        f.write_str(&self.elements.render(Observation::to_char))?;
         f.write_char('\n')?;
         Ok(())
     }
@@ -46,14 +47,16 @@ impl Display for Platform {
 impl Platform {
     pub fn tilted(self) -> Platform {
         let mut elements = self.elements;
-        let n_cols = elements[0].len();
-        for _ in (1..elements.len()).rev() {
-            for row_n in (1..elements.len()).rev() {
+        let n_cols = elements.width();
+        for _ in (1..elements.height()).rev() {
+            for row_n in (1..elements.height()).rev() {
                 // Swap elements
                 for col_n in 0..n_cols {
-                    if elements[row_n][col_n] == RoundRock && elements[row_n - 1][col_n] == Empty {
-                        elements[row_n - 1][col_n] = RoundRock;
-                        elements[row_n][col_n] = Empty;
+                    if elements[(row_n, col_n)] == RoundRock
+                        && elements[(row_n - 1, col_n)] == Empty
+                    {
+                        elements[(row_n - 1, col_n)] = RoundRock;
+                        elements[(row_n, col_n)] = Empty;
                     }
                 }
             }
@@ -63,29 +66,31 @@ impl Platform {
 
     pub fn total_load(&self) -> usize {
         // Get load for every row, by counting the amount of rocks and multiplying by the weight
-        let n_rows = self.elements.len();
+        let n_rows = self.elements.height();
         let weights = (1..=n_rows).rev();
         self.elements
-            .iter()
+            .rows()
             .zip(weights)
             .map(|(row, weight)| row.iter().filter(|&o| o == &RoundRock).count() * weight)
             .sum()
     }
 }
 impl FromStr for Platform {
-    type Err = &'static str;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let elements = s
-            .trim()
-            .lines()
-            .map(|line| {
-                line.trim()
-                    .chars()
-                    .map(Observation::from_char)
-                    .collect::<Result<Vec<Observation>, &'static str>>()
-            })
-            .collect::<Result<Vec<Vec<Observation>>, &'static str>>()?;
+        let elements =
+            Grid::parse(s, "platform", Observation::from_char).map_err(|e| e.in_day(DAY))?;
         Ok(Platform { elements })
     }
 }
+
+impl Solution for Platform {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.clone().tilted().total_load()))
+    }
+}
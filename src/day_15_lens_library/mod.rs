@@ -1,3 +1,5 @@
+use crate::error::AocError;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 use std::str::FromStr;
 
@@ -29,7 +31,7 @@ impl InitializationSequence {
     }
 }
 impl FromStr for InitializationSequence {
-    type Err = &'static str;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let steps = s
@@ -40,3 +42,13 @@ impl FromStr for InitializationSequence {
         Ok(InitializationSequence { steps })
     }
 }
+
+impl Solution for InitializationSequence {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.sum_of_hashes()))
+    }
+}
@@ -1,9 +1,14 @@
 use crate::day_18_lavaduct_lagoon::Direction::{East, North, South, West};
+use crate::error::{AocError, ErrorKind};
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 use std::cmp::{max, min};
 use std::fmt::{Display, Formatter, Write};
 use std::str::FromStr;
 
+const DAY: usize = 18;
+
 #[derive(PartialEq, Clone)]
 enum Block {
     Air,
@@ -18,33 +23,28 @@ impl Display for Block {
     }
 }
 pub struct Terrain {
-    grid: Vec<Vec<Block>>,
+    grid: Grid<Block>,
 }
 
 impl Display for Terrain {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in &self.grid {
-            for block in row {
-                write!(f, "{}", block)?;
-            }
-            write!(f, "\n")?;
-        }
-        Ok(())
+        write!(f, "{}", self.grid)
     }
 }
 
 impl Terrain {
     pub fn is_inside_tench(&self, row_n: usize, col_n: usize) -> bool {
-        if self.grid[row_n][col_n] == Block::Air {
+        if self.grid[(row_n, col_n)] == Block::Air {
             return true;
         }
 
         // Last element in row, its not in trench
-        if col_n + 1 == self.grid[0].len() {
+        if col_n + 1 == self.grid.width() {
             return false;
         }
 
-        let left_over_row = &self.grid[row_n][col_n + 1..];
+        let the_row = self.grid.row(row_n).unwrap_or_default();
+        let left_over_row = &the_row[col_n + 1..];
         // Gotta dedupe consecutive edges
         let mut left_over_row_no_consecutives = vec![];
         let mut last_was_air = false;
@@ -56,7 +56,7 @@ impl Terrain {
             }
             last_was_air = block == &Block::Air;
         }
-        let odd_edges_seen = self.grid[row_n][col_n + 1..]
+        let odd_edges_seen = left_over_row
             .iter()
             .filter(|&block| block == &Block::Air)
             .count()
@@ -67,35 +67,33 @@ impl Terrain {
     }
     pub fn culled(self) -> Terrain {
         // For each point in the terrain, check if its inside or outside, if inside paint
-        let height = self.grid.len();
-        let width = self.grid[0].len();
-
         let mut new_terrain = self;
 
-        let coordinates_inside_trench: Vec<(usize, usize)> = (0..height)
-            .cartesian_product(0..width)
+        let coordinates_inside_trench: Vec<(usize, usize)> = new_terrain
+            .grid
+            .positions()
             .filter(|(row_n, col_n)| new_terrain.is_inside_tench(*row_n, *col_n))
             .collect_vec();
         coordinates_inside_trench
             .iter()
-            .for_each(|(row_n, col_n)| new_terrain.grid[*row_n][*col_n] = Block::Air);
+            .for_each(|&position| new_terrain.grid[position] = Block::Air);
         new_terrain
     }
 
     pub fn trench_area(&self) -> usize {
         self.grid
             .iter()
-            .flat_map(|row| row.iter().filter(|&block| block == &Block::Air))
+            .filter(|(_, block)| block == &&Block::Air)
             .count()
     }
 
     pub fn empty(n_rows: usize, n_cols: usize) -> Terrain {
-        let grid = vec![vec![Block::Ground; n_cols]; n_rows];
+        let grid = Grid::new(n_rows, n_cols, Block::Ground);
         Terrain { grid }
     }
 
     pub fn dig(&mut self, row_n: usize, col_n: usize) {
-        self.grid[row_n][col_n] = Block::Air;
+        self.grid[(row_n, col_n)] = Block::Air;
     }
 }
 
@@ -107,18 +105,27 @@ enum Direction {
 }
 
 impl FromStr for Direction {
-    type Err = &'static str;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unknown_direction = || {
+            AocError::day(
+                DAY,
+                ErrorKind::UnknownToken {
+                    what: "direction",
+                    token: s.to_string(),
+                },
+            )
+        };
         if s.trim().len() != 1 {
-            return Err("Unable to parse more than one character for direction");
+            return Err(unknown_direction());
         }
         match &s.chars().next().unwrap().to_uppercase().next().unwrap() {
             'U' => Ok(North),
             'D' => Ok(South),
             'L' => Ok(West),
             'R' => Ok(East),
-            _ => Err("Unable to parse direction from character!"),
+            _ => Err(unknown_direction()),
         }
     }
 }
@@ -129,18 +136,26 @@ pub struct RgbColor {
 }
 
 impl FromStr for RgbColor {
-    type Err = &'static str;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unable_to_parse_error = || {
+            AocError::day(
+                DAY,
+                ErrorKind::InvalidNumber {
+                    what: "hex color",
+                    token: s.to_string(),
+                },
+            )
+        };
         // Takes in a string that looks like FFFFFF
-        if s.len() != 6 && !s.chars().all(|c| c.is_ascii_hexdigit()) {
-            return Err("Must be a 6 character hex string");
+        if s.len() != 6 || !s.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(unable_to_parse_error());
         }
         // There has to be three chunks
-        let unable_to_parse_error = |_| "Unable to parse hex";
-        let r: u8 = u8::from_str_radix(&s[0..=1], 16).map_err(unable_to_parse_error)?;
-        let g: u8 = u8::from_str_radix(&s[2..=3], 16).map_err(unable_to_parse_error)?;
-        let b: u8 = u8::from_str_radix(&s[4..=5], 16).map_err(unable_to_parse_error)?;
+        let r: u8 = u8::from_str_radix(&s[0..=1], 16).map_err(|_| unable_to_parse_error())?;
+        let g: u8 = u8::from_str_radix(&s[2..=3], 16).map_err(|_| unable_to_parse_error())?;
+        let b: u8 = u8::from_str_radix(&s[4..=5], 16).map_err(|_| unable_to_parse_error())?;
         Ok(Self { r, g, b })
     }
 }
@@ -170,21 +185,36 @@ impl Step {
 }
 
 impl FromStr for Step {
-    type Err = &'static str;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Takes a row in the form R 6 (#70c710), and parses the tokens
-        let tokens_error = || "Unable to get exactly three tokens from step!";
         let tokened_string = s.trim().replace("(#", "").replace(")", "");
+        let tokens_error = || {
+            AocError::day(
+                DAY,
+                ErrorKind::WrongTokenCount {
+                    what: "step",
+                    expected: 3,
+                    found: tokened_string.split_whitespace().count(),
+                },
+            )
+        };
         let (direction_token, count_token, color_token): (&str, &str, &str) = tokened_string
             .split_whitespace()
             .collect_tuple()
             .ok_or_else(tokens_error)?;
 
         let direction = direction_token.parse()?;
-        let count = count_token
-            .parse()
-            .map_err(|_| "Unable to parse step count")?;
+        let count = count_token.parse().map_err(|_| {
+            AocError::day(
+                DAY,
+                ErrorKind::InvalidNumber {
+                    what: "step count",
+                    token: count_token.to_string(),
+                },
+            )
+        })?;
         let color = color_token.parse()?;
         Ok(Self {
             direction,
@@ -198,20 +228,21 @@ pub struct DigPlan {
 }
 
 impl FromStr for DigPlan {
-    type Err = &'static str;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let ss = s;
         let steps = s
             .lines()
-            .map(|line| line.parse())
-            .collect::<Result<Vec<Step>, &'static str>>()?;
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_n, line)| line.parse().map_err(|e: AocError| e.at_line(line_n + 1)))
+            .collect::<Result<Vec<Step>, AocError>>()?;
         Ok(Self { steps })
     }
 }
 
 impl DigPlan {
-    pub fn apply(self, only_vertices: bool) -> Terrain {
+    pub fn apply(&self, only_vertices: bool) -> Terrain {
         // Will go through a dig plan creating coordinates for the trench
         let mut min_row = 0;
         let mut min_column = 0;
@@ -221,7 +252,7 @@ impl DigPlan {
         let mut current_column = 0;
         let mut coordinates = vec![];
 
-        for step in self.steps {
+        for step in &self.steps {
             for new_cord in step.apply(current_row, current_column, only_vertices) {
                 (current_row, current_column) = new_cord;
                 coordinates.push((current_row, current_column));
@@ -247,3 +278,13 @@ impl DigPlan {
         the_terrain
     }
 }
+
+impl Solution for DigPlan {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.apply(false).culled().trench_area()))
+    }
+}
@@ -1,45 +1,166 @@
-const DIGIT_WORDS: [&str; 10] = [
-    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-];
+use crate::error::AocError;
+use crate::solution::{Answer, Solution};
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+mod report;
+mod scanner;
+mod stream;
+mod vocabulary;
+
+pub use report::{CalibrationReport, LineReport, MatchedToken, TokenKind};
+pub use scanner::{fold_case, DigitMatch, DigitScanner};
+pub use vocabulary::Vocabulary;
+
+const DAY: usize = 1;
+
+const DIGIT_CHARS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
 
 /*
-   Takes in a string slice, and returns the first digit substring
-   it finds. The first digit can come either as a digit char, or a word
-   (E.g. one, two, three)
+    How digits are found in the calibration document: digit chars always count,
+    and the words of the vocabulary count too while spelled out words are on
 */
-fn get_first_digit_substring(the_buffer: &str) -> Option<u32> {
-    let lowercase_buffer: String = the_buffer.to_lowercase();
-    // First check the first char, to see if it is a digit
-    if let Some(digit) = the_buffer.chars().next()?.to_digit(10) {
-        return Some(digit);
+#[derive(Debug, Clone)]
+pub struct TrebuchetConfig {
+    vocabulary: Vocabulary,
+    spelled_out: bool,
+    scanner: DigitScanner,
+}
+
+impl TrebuchetConfig {
+    pub fn new(vocabulary: Vocabulary) -> TrebuchetConfig {
+        TrebuchetConfig::build(vocabulary, true)
+    }
+
+    // Only digit chars, the way part one reads the document
+    pub fn digits_only() -> TrebuchetConfig {
+        TrebuchetConfig::build(Vocabulary::english(), false)
+    }
+
+    fn build(vocabulary: Vocabulary, spelled_out: bool) -> TrebuchetConfig {
+        let words = vocabulary.words().filter(|_| spelled_out);
+        let scanner = DigitScanner::new(DIGIT_CHARS.into_iter().zip(0..).chain(words));
+        TrebuchetConfig {
+            vocabulary,
+            spelled_out,
+            scanner,
+        }
+    }
+
+    // Turns matching the words of the vocabulary on or off
+    pub fn spelled_out(self, spelled_out: bool) -> TrebuchetConfig {
+        TrebuchetConfig::build(self.vocabulary, spelled_out)
+    }
+
+    pub fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    pub fn is_spelled_out(&self) -> bool {
+        self.spelled_out
+    }
+
+    // Every digit in the line, overlapping words included, in the order they start
+    pub fn digits(&self, line: &str) -> Vec<u32> {
+        self.scanner
+            .matches(line)
+            .into_iter()
+            .map(|found| found.value)
+            .collect()
+    }
+
+    // Only the first and the last digit matter, so the line gets scanned from each end
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        let first = self.scanner.first(line)?.value;
+        let last = self.scanner.last(line)?.value;
+        Some(first * 10 + last)
+    }
+
+    // Lines without any digit don't add anything
+    pub fn calibration_sum(&self, document: &str) -> u32 {
+        document
+            .lines()
+            .filter_map(|line| self.calibration_value(line))
+            .sum()
+    }
+
+    // The first and last token of the line, with where they are in it
+    pub fn line_report(&self, line_n: usize, line: &str) -> Option<LineReport> {
+        Some(LineReport {
+            line: line_n,
+            first: MatchedToken::new(line, self.scanner.first(line)?),
+            last: MatchedToken::new(line, self.scanner.last(line)?),
+        })
+    }
+
+    /*
+        Reads the document the same way as calibration_sum, but keeps how every line was read,
+        lines without any digit included
+    */
+    pub fn report(&self, document: &str) -> CalibrationReport {
+        let mut report = CalibrationReport::default();
+        for (line_n, line) in document.lines().enumerate() {
+            match self.line_report(line_n + 1, line) {
+                Some(line_report) => report.lines.push(line_report),
+                None => report.lines_without_digits.push(line_n + 1),
+            }
+        }
+        report
     }
-    DIGIT_WORDS
-        .iter()
-        .position(|digit_word: &&str| lowercase_buffer.starts_with(digit_word))
-        .map(|num| num as u32)
 }
 
+// Digits and english words, the way part two reads the document
+impl Default for TrebuchetConfig {
+    fn default() -> TrebuchetConfig {
+        TrebuchetConfig::new(Vocabulary::english())
+    }
+}
+
+// Built the first time a line gets scanned, and shared from then on
+static SPELLED_OUT: LazyLock<TrebuchetConfig> = LazyLock::new(TrebuchetConfig::default);
+static DIGITS_ONLY: LazyLock<TrebuchetConfig> = LazyLock::new(TrebuchetConfig::digits_only);
+
 /*
     From a string, it returns all the digit substrings
-     in the string. Possible digit substrings are "1", "2", "two", "four".
+     in the string, overlapping ones included. Possible digit substrings are "1", "2", "two", "four".
 */
-fn get_all_digits(the_buffer: &str) -> Vec<u32> {
-    (0..the_buffer.len())
-        .map(|i| &the_buffer[i..])
-        .map(get_first_digit_substring)
-        .flatten()
-        .collect()
+pub fn get_all_digits(the_buffer: &str) -> Vec<u32> {
+    SPELLED_OUT.digits(the_buffer)
 }
 
 pub fn get_trebuchet(trebuchet: &str) -> Option<u32> {
-    let all_digits: Vec<u32> = get_all_digits(trebuchet);
+    SPELLED_OUT.calibration_value(trebuchet)
+}
+
+pub fn get_trebuchet_multiple(trebuchet: &str) -> u32 {
+    SPELLED_OUT.calibration_sum(trebuchet)
+}
 
-    let (first, last) = (all_digits.first()?, all_digits.last()?);
+// Sums a document of any size, a line at a time
+pub fn get_trebuchet_multiple_from(reader: impl BufRead) -> Result<u64, AocError> {
+    SPELLED_OUT.calibration_sum_from(reader)
+}
 
-    Some(first * 10 + last)
+pub fn get_trebuchet_with(trebuchet: &str, config: &TrebuchetConfig) -> Option<u32> {
+    config.calibration_value(trebuchet)
 }
 
-pub fn get_trebuchet_multiple(trebuchet: &str) -> u32 {
-    let list_of_line_sums: Vec<u32> = trebuchet.lines().filter_map(get_trebuchet).collect();
-    return list_of_line_sums.iter().sum();
+pub fn get_trebuchet_multiple_with(trebuchet: &str, config: &TrebuchetConfig) -> u32 {
+    config.calibration_sum(trebuchet)
+}
+
+pub struct CalibrationDocument(String);
+
+impl Solution for CalibrationDocument {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(CalibrationDocument(input.to_string()))
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(DIGITS_ONLY.calibration_sum(&self.0)))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(get_trebuchet_multiple(&self.0)))
+    }
 }
@@ -0,0 +1,194 @@
+use crate::day_1_trebuchet::DigitMatch;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Digit,
+    Word,
+}
+
+impl TokenKind {
+    fn name(self) -> &'static str {
+        match self {
+            TokenKind::Digit => "digit",
+            TokenKind::Word => "word",
+        }
+    }
+}
+
+/*
+    A token as it was written in the line, with the byte range it takes there
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchedToken {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+    pub kind: TokenKind,
+}
+
+impl MatchedToken {
+    pub fn new(line: &str, found: DigitMatch) -> MatchedToken {
+        let text = &line[found.start..found.start + found.len];
+        let kind = if text.len() == 1 && text.as_bytes()[0].is_ascii_digit() {
+            TokenKind::Digit
+        } else {
+            TokenKind::Word
+        };
+        MatchedToken {
+            text: text.to_string(),
+            start: found.start,
+            end: found.start + found.len,
+            value: found.value,
+            kind,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"text\": {}, \"start\": {}, \"end\": {}, \"value\": {}, \"kind\": \"{}\"}}",
+            json_string(&self.text),
+            self.start,
+            self.end,
+            self.value,
+            self.kind.name()
+        )
+    }
+}
+
+// The tokens a line's calibration value comes from. Lines are numbered from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineReport {
+    pub line: usize,
+    pub first: MatchedToken,
+    pub last: MatchedToken,
+}
+
+impl LineReport {
+    pub fn value(&self) -> u32 {
+        self.first.value * 10 + self.last.value
+    }
+}
+
+/*
+    How every line of a calibration document was read: the lines with a calibration value,
+    and the ones that have no digit at all and so don't add to the sum
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CalibrationReport {
+    pub lines: Vec<LineReport>,
+    pub lines_without_digits: Vec<usize>,
+}
+
+impl CalibrationReport {
+    pub fn sum(&self) -> u32 {
+        self.lines.iter().map(LineReport::value).sum()
+    }
+
+    /*
+        One row per line with a value, with the byte range of each token after it,
+        and the sum and the lines without digits at the bottom
+    */
+    pub fn to_table(&self) -> String {
+        let width = |header: &str, token: fn(&LineReport) -> &MatchedToken| {
+            self.lines
+                .iter()
+                .map(|line| token(line).text.chars().count())
+                .chain([header.len()])
+                .max()
+                .unwrap_or_default()
+        };
+        let first_width = width("First", |line| &line.first);
+        let last_width = width("Last", |line| &line.last);
+        let mut table = String::new();
+        let mut row = |cells: [String; 8]| {
+            let [line, first, first_kind, first_bytes, last, last_kind, last_bytes, value] = cells;
+            writeln!(
+                table,
+                "{:>5}  {:<first_width$}  {:<5}  {:<8}  {:<last_width$}  {:<5}  {:<8}  {:>5}",
+                line, first, first_kind, first_bytes, last, last_kind, last_bytes, value
+            )
+            .unwrap();
+        };
+        row([
+            "Line", "First", "Kind", "Bytes", "Last", "Kind", "Bytes", "Value",
+        ]
+        .map(str::to_string));
+        let bytes = |token: &MatchedToken| format!("{}..{}", token.start, token.end);
+        for line in &self.lines {
+            row([
+                line.line.to_string(),
+                line.first.text.clone(),
+                line.first.kind.name().to_string(),
+                bytes(&line.first),
+                line.last.text.clone(),
+                line.last.kind.name().to_string(),
+                bytes(&line.last),
+                line.value().to_string(),
+            ]);
+        }
+        writeln!(table, "Sum: {}", self.sum()).unwrap();
+        let without_digits = match self.lines_without_digits.as_slice() {
+            [] => "none".to_string(),
+            lines => lines
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        writeln!(table, "Lines without digits: {}", without_digits).unwrap();
+        table
+    }
+
+    // The same report as a JSON object, with one line per calibrated line
+    pub fn to_json(&self) -> String {
+        let lines: Vec<String> = self
+            .lines
+            .iter()
+            .map(|line| {
+                format!(
+                    "    {{\"line\": {}, \"value\": {}, \"first\": {}, \"last\": {}}}",
+                    line.line,
+                    line.value(),
+                    line.first.to_json(),
+                    line.last.to_json()
+                )
+            })
+            .collect();
+        let lines = if lines.is_empty() {
+            "[]".to_string()
+        } else {
+            format!("[\n{}\n  ]", lines.join(",\n"))
+        };
+        let without_digits: Vec<String> = self
+            .lines_without_digits
+            .iter()
+            .map(usize::to_string)
+            .collect();
+        format!(
+            "{{\n  \"sum\": {},\n  \"lines\": {},\n  \"lines_without_digits\": [{}]\n}}\n",
+            self.sum(),
+            lines,
+            without_digits.join(", ")
+        )
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for character in text.chars() {
+        match character {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,297 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+const NO_STATE: u32 = u32::MAX;
+
+// A scan of the raw bytes gave up, the line has to be case folded first
+struct NotAscii;
+
+/*
+    Folds the case of the text, so words match whatever case they were written in.
+    Every char is lowercased on its own, with the final sigma and the sharp s
+    folded the way they compare: "ΤΡΕΙΣ" is "τρεισ", and "STRASSE" and "straße" are "strasse".
+    ASCII text is left as it is, the scanner lowercases it as it goes
+*/
+pub fn fold_case(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let mut folded = String::with_capacity(text.len());
+    for character in text.chars() {
+        push_folded(&mut folded, character);
+    }
+    Cow::Owned(folded)
+}
+
+fn push_folded(folded: &mut String, character: char) {
+    for lower in character.to_lowercase() {
+        match lower {
+            'ς' => folded.push('σ'),
+            'ß' => folded.push_str("ss"),
+            _ => folded.push(lower),
+        }
+    }
+}
+
+// The folded text, and for each of its bytes the offset of the char of the text it was folded from
+fn fold_case_with_origins(text: &str) -> (String, Vec<usize>) {
+    let mut folded = String::with_capacity(text.len());
+    let mut origins = Vec::with_capacity(text.len());
+    for (offset, character) in text.char_indices() {
+        push_folded(&mut folded, character);
+        origins.resize(folded.len(), offset);
+    }
+    (folded, origins)
+}
+
+// Takes a match in the folded line back to the chars of the line it was folded from
+fn unfold(line: &str, origins: &[usize], found: DigitMatch) -> DigitMatch {
+    let start = origins[found.start];
+    let last_char = origins[found.start + found.len - 1];
+    let end = last_char + line[last_char..].chars().next().map_or(1, char::len_utf8);
+    DigitMatch {
+        start,
+        len: end - start,
+        value: found.value,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitMatch {
+    // Byte offset of the token in the line, and how many bytes of the line it takes.
+    // Folding can change the length of a char, so these can differ from the token's own length
+    pub start: usize,
+    pub len: usize,
+    pub value: u32,
+}
+
+/*
+    A deterministic automaton: every state has a transition for every byte,
+    so scanning never has to follow failure links
+*/
+#[derive(Debug, Clone)]
+struct Automaton {
+    transitions: Vec<[u32; 256]>,
+    // The longest token ending at the state, as (length, value)
+    longest: Vec<Option<(usize, u32)>>,
+    // The token ending at the state itself, and the next state down the failure chain with a token
+    own: Vec<Option<(usize, u32)>>,
+    next_output: Vec<u32>,
+    max_len: usize,
+}
+
+impl Automaton {
+    fn new<'a>(tokens: impl IntoIterator<Item = (&'a [u8], u32)>) -> Automaton {
+        let mut transitions = vec![[NO_STATE; 256]];
+        let mut own: Vec<Option<(usize, u32)>> = vec![None];
+        let mut max_len = 0;
+        for (token, value) in tokens {
+            if token.is_empty() {
+                continue;
+            }
+            let mut state = 0;
+            for byte in token.iter().map(u8::to_ascii_lowercase) {
+                if transitions[state][byte as usize] == NO_STATE {
+                    transitions.push([NO_STATE; 256]);
+                    own.push(None);
+                    transitions[state][byte as usize] = (transitions.len() - 1) as u32;
+                }
+                state = transitions[state][byte as usize] as usize;
+            }
+            // A repeated token keeps the value it was first given
+            own[state].get_or_insert((token.len(), value));
+            max_len = max_len.max(token.len());
+        }
+
+        // Breadth first, so the failure state of every state is done before the state itself
+        let states = transitions.len();
+        let mut fail = vec![0u32; states];
+        let mut longest = own.clone();
+        let mut next_output = vec![NO_STATE; states];
+        let mut queue = VecDeque::new();
+        for next in transitions[0].iter_mut() {
+            match *next {
+                NO_STATE => *next = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let fail_state = fail[state] as usize;
+            longest[state] = longest[state].or(longest[fail_state]);
+            next_output[state] = if own[fail_state].is_some() {
+                fail_state as u32
+            } else {
+                next_output[fail_state]
+            };
+            let fail_transitions = transitions[fail_state];
+            for (next, fail_next) in transitions[state].iter_mut().zip(fail_transitions) {
+                match *next {
+                    NO_STATE => *next = fail_next,
+                    child => {
+                        fail[child as usize] = fail_next;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+        Automaton {
+            transitions,
+            longest,
+            own,
+            next_output,
+            max_len,
+        }
+    }
+
+    fn step(&self, state: usize, byte: u8) -> usize {
+        self.transitions[state][byte.to_ascii_lowercase() as usize] as usize
+    }
+}
+
+/*
+    Finds digit tokens (like "7" or "seven") in a line in a single pass, with an Aho-Corasick
+    automaton over the bytes of the line. Tokens are matched ignoring case (see fold_case), and
+    overlapping tokens are all found, so "twone" has both a 2 and a 1
+*/
+#[derive(Debug, Clone)]
+pub struct DigitScanner {
+    forward: Automaton,
+    // Built from the reversed tokens, to scan lines from their end
+    backward: Automaton,
+}
+
+impl DigitScanner {
+    pub fn new<'a>(tokens: impl IntoIterator<Item = (&'a str, u32)>) -> DigitScanner {
+        let folded: Vec<(Cow<str>, u32)> = tokens
+            .into_iter()
+            .map(|(token, value)| (fold_case(token), value))
+            .collect();
+        let reversed: Vec<(Vec<u8>, u32)> = folded
+            .iter()
+            .map(|(token, value)| (token.bytes().rev().collect(), *value))
+            .collect();
+        DigitScanner {
+            forward: Automaton::new(
+                folded
+                    .iter()
+                    .map(|(token, value)| (token.as_bytes(), *value)),
+            ),
+            backward: Automaton::new(
+                reversed
+                    .iter()
+                    .map(|(token, value)| (token.as_slice(), *value)),
+            ),
+        }
+    }
+
+    /*
+        The token that starts first, the longest one if several start at the same place.
+        Once a match is found, the scan only goes on while a longer token could still start before it
+    */
+    pub fn first(&self, line: &str) -> Option<DigitMatch> {
+        // Folding costs a pass over the line, so it's only done once there's a non ASCII byte
+        self.scan_first(line.as_bytes(), true)
+            .unwrap_or_else(|NotAscii| {
+                let (folded, origins) = fold_case_with_origins(line);
+                self.scan_first(folded.as_bytes(), false)
+                    .unwrap_or(None)
+                    .map(|found| unfold(line, &origins, found))
+            })
+    }
+
+    /*
+        The token that starts last, the longest one if several start at the same place.
+        Scanning the reversed line, the first token to end is the one that starts last
+    */
+    pub fn last(&self, line: &str) -> Option<DigitMatch> {
+        self.scan_last(line.as_bytes(), true)
+            .unwrap_or_else(|NotAscii| {
+                let (folded, origins) = fold_case_with_origins(line);
+                self.scan_last(folded.as_bytes(), false)
+                    .unwrap_or(None)
+                    .map(|found| unfold(line, &origins, found))
+            })
+    }
+
+    fn scan_first(&self, bytes: &[u8], ascii_only: bool) -> Result<Option<DigitMatch>, NotAscii> {
+        let automaton = &self.forward;
+        let mut state = 0;
+        let mut best: Option<DigitMatch> = None;
+        for (i, &byte) in bytes.iter().enumerate() {
+            if best.is_some_and(|best| i >= best.start + automaton.max_len) {
+                break;
+            }
+            if ascii_only && !byte.is_ascii() {
+                return Err(NotAscii);
+            }
+            state = automaton.step(state, byte);
+            if let Some((len, value)) = automaton.longest[state] {
+                let start = i + 1 - len;
+                // A later match starting at the same place is a longer one
+                if best.is_none_or(|best| start <= best.start) {
+                    best = Some(DigitMatch { start, len, value });
+                }
+            }
+        }
+        Ok(best)
+    }
+
+    fn scan_last(&self, bytes: &[u8], ascii_only: bool) -> Result<Option<DigitMatch>, NotAscii> {
+        let automaton = &self.backward;
+        let mut state = 0;
+        for (i, &byte) in bytes.iter().enumerate().rev() {
+            if ascii_only && !byte.is_ascii() {
+                return Err(NotAscii);
+            }
+            state = automaton.step(state, byte);
+            if let Some((len, value)) = automaton.longest[state] {
+                return Ok(Some(DigitMatch {
+                    start: i,
+                    len,
+                    value,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    // Every token in the line, overlapping ones included, in the order they start
+    pub fn matches(&self, line: &str) -> Vec<DigitMatch> {
+        if line.is_ascii() {
+            return self.scan_all(line.as_bytes());
+        }
+        let (folded, origins) = fold_case_with_origins(line);
+        let mut found: Vec<DigitMatch> = self
+            .scan_all(folded.as_bytes())
+            .into_iter()
+            .map(|found| unfold(line, &origins, found))
+            .collect();
+        // Tokens in different parts of a folded char (like the two 's' of a 'ß') are the same in the line
+        found.dedup();
+        found
+    }
+
+    fn scan_all(&self, bytes: &[u8]) -> Vec<DigitMatch> {
+        let automaton = &self.forward;
+        let mut state = 0;
+        let mut found = vec![];
+        for (i, &byte) in bytes.iter().enumerate() {
+            state = automaton.step(state, byte);
+            let mut output_state = state;
+            while output_state != NO_STATE as usize {
+                if let Some((len, value)) = automaton.own[output_state] {
+                    found.push(DigitMatch {
+                        start: i + 1 - len,
+                        len,
+                        value,
+                    });
+                }
+                output_state = automaton.next_output[output_state] as usize;
+            }
+        }
+        // Matches come out in the order they end, the longest first for each end
+        found.sort_by_key(|found| (found.start, Reverse(found.len)));
+        found
+    }
+}
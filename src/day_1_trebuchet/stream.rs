@@ -0,0 +1,127 @@
+use crate::day_1_trebuchet::{TrebuchetConfig, DAY};
+use crate::error::{AocError, ErrorKind};
+use crate::inputs::for_each_line;
+use std::io::BufRead;
+use std::sync::{mpsc, Mutex};
+use std::{mem, panic, thread};
+
+// How many lines a worker gets at a time, enough to make handing them over cheap
+const BATCH_LINES: usize = 4096;
+
+fn checked_add(total: u64, value: u64) -> Result<u64, AocError> {
+    total.checked_add(value).ok_or_else(|| {
+        AocError::day(
+            DAY,
+            ErrorKind::Overflow {
+                what: "calibration sum",
+            },
+        )
+    })
+}
+
+impl TrebuchetConfig {
+    /*
+        Same as calibration_sum, but reading the document a line at a time,
+        so it doesn't have to fit in memory
+    */
+    pub fn calibration_sum_from(&self, reader: impl BufRead) -> Result<u64, AocError> {
+        self.add_calibration_sum(0, reader)
+    }
+
+    /*
+        Adds the values of the reader to a running total, for documents split across several files.
+        Fails on the line where the total stops fitting
+    */
+    pub fn add_calibration_sum(&self, total: u64, reader: impl BufRead) -> Result<u64, AocError> {
+        let mut total = total;
+        for_each_line(DAY, reader, |line_n, line| {
+            total = checked_add(total, self.calibration_value(line).map_or(0, u64::from))
+                .map_err(|e| e.at_line(line_n))?;
+            Ok(())
+        })?;
+        Ok(total)
+    }
+
+    /*
+        Same as calibration_sum_from, with the lines handed in batches to the given amount of threads.
+        The reader stays ahead of the workers by only a couple of batches each, so memory stays bounded
+    */
+    pub fn calibration_sum_parallel(
+        &self,
+        reader: impl BufRead,
+        threads: usize,
+    ) -> Result<u64, AocError> {
+        if threads <= 1 {
+            return self.calibration_sum_from(reader);
+        }
+        let (sender, receiver) = mpsc::sync_channel::<(usize, Vec<String>)>(threads * 2);
+        let receiver = Mutex::new(receiver);
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut total = 0;
+                        loop {
+                            // The lock is let go before the batch gets worked on
+                            let batch = receiver.lock().unwrap().recv();
+                            // The channel closes once the reader is done
+                            let Ok((first_line, lines)) = batch else {
+                                break;
+                            };
+                            for (line_n, line) in (first_line..).zip(&lines) {
+                                total = checked_add(
+                                    total,
+                                    self.calibration_value(line).map_or(0, u64::from),
+                                )
+                                .map_err(|e| e.at_line(line_n))?;
+                            }
+                        }
+                        Ok(total)
+                    })
+                })
+                .collect();
+
+            let mut batch = Vec::with_capacity(BATCH_LINES);
+            let mut first_line = 1;
+            let send = |first_line: usize, batch: Vec<String>| {
+                // Only fails once every worker has stopped on an error, which gets reported instead
+                sender
+                    .send((first_line, batch))
+                    .map_err(|_| AocError::invalid(DAY, "the workers stopped"))
+            };
+            let read = for_each_line(DAY, reader, |line_n, line| {
+                if batch.is_empty() {
+                    first_line = line_n;
+                }
+                batch.push(line.to_string());
+                if batch.len() == BATCH_LINES {
+                    send(first_line, mem::take(&mut batch))?;
+                }
+                Ok(())
+            })
+            .and_then(|_| {
+                if batch.is_empty() {
+                    Ok(())
+                } else {
+                    send(first_line, batch)
+                }
+            });
+            drop(sender);
+
+            let totals: Vec<Result<u64, AocError>> = workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+                .collect();
+            // Of the errors, the one on the earliest line is the one to fix first
+            if let Some(error) = totals
+                .iter()
+                .filter_map(|total| total.as_ref().err())
+                .min_by_key(|error| error.line)
+            {
+                return Err(error.clone());
+            }
+            read?;
+            totals.into_iter().flatten().try_fold(0, checked_add)
+        })
+    }
+}
@@ -0,0 +1,148 @@
+use crate::day_1_trebuchet::DAY;
+use crate::error::{AocError, ErrorKind};
+use std::str::FromStr;
+
+const ENGLISH: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const SPANISH: [&str; 10] = [
+    "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+];
+const GERMAN: [&str; 10] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+const FRENCH: [&str; 10] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+];
+
+/*
+    The spelled out words that count as digits, each with the digit it stands for.
+    Several words can stand for the same digit, e.g. after merging two languages
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    pub fn empty() -> Vocabulary {
+        Vocabulary::default()
+    }
+
+    fn from_digit_words(words: [&str; 10]) -> Vocabulary {
+        Vocabulary {
+            words: words.iter().map(|word| word.to_string()).zip(0..).collect(),
+        }
+    }
+
+    pub fn english() -> Vocabulary {
+        Vocabulary::from_digit_words(ENGLISH)
+    }
+
+    pub fn spanish() -> Vocabulary {
+        Vocabulary::from_digit_words(SPANISH)
+    }
+
+    pub fn german() -> Vocabulary {
+        Vocabulary::from_digit_words(GERMAN)
+    }
+
+    pub fn french() -> Vocabulary {
+        Vocabulary::from_digit_words(FRENCH)
+    }
+
+    // One of the built in vocabularies, by its ISO 639-1 code
+    pub fn for_language(code: &str) -> Option<Vocabulary> {
+        match code.to_ascii_lowercase().as_str() {
+            "en" => Some(Vocabulary::english()),
+            "es" => Some(Vocabulary::spanish()),
+            "de" => Some(Vocabulary::german()),
+            "fr" => Some(Vocabulary::french()),
+            _ => None,
+        }
+    }
+
+    /*
+        Adds a word of your own. It has to have something besides whitespace,
+        and stand for a single digit
+    */
+    pub fn add(&mut self, word: &str, digit: u32) -> Result<(), AocError> {
+        let word = word.trim();
+        if word.is_empty() {
+            return Err(AocError::day(DAY, ErrorKind::Empty { what: "digit word" }));
+        }
+        if digit > 9 {
+            return Err(AocError::day(
+                DAY,
+                ErrorKind::InvalidNumber {
+                    what: "digit",
+                    token: digit.to_string(),
+                },
+            ));
+        }
+        self.words.push((word.to_string(), digit));
+        Ok(())
+    }
+
+    pub fn with(mut self, other: &Vocabulary) -> Vocabulary {
+        self.words.extend(other.words.iter().cloned());
+        self
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> + '_ {
+        self.words
+            .iter()
+            .map(|(word, digit)| (word.as_str(), *digit))
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/*
+    Reads a vocabulary with a word and its digit per line, e.g. "uno 1".
+    Blank lines and lines starting with '#' are skipped
+*/
+impl FromStr for Vocabulary {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut vocabulary = Vocabulary::empty();
+        for (line_n, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let [word, digit] = tokens[..] else {
+                return Err(AocError::day(
+                    DAY,
+                    ErrorKind::WrongTokenCount {
+                        what: "vocabulary entry",
+                        expected: 2,
+                        found: tokens.len(),
+                    },
+                )
+                .at_line(line_n + 1));
+            };
+            let digit = digit.parse().map_err(|_| {
+                AocError::day(
+                    DAY,
+                    ErrorKind::InvalidNumber {
+                        what: "digit",
+                        token: digit.to_string(),
+                    },
+                )
+            });
+            digit
+                .and_then(|digit| vocabulary.add(word, digit))
+                .map_err(|e| e.at_line(line_n + 1))?;
+        }
+        Ok(vocabulary)
+    }
+}
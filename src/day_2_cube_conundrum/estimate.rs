@@ -0,0 +1,355 @@
+use crate::day_2_cube_conundrum::{Conundrum, CubeColor, CubeCounts, Palette, DAY};
+use crate::error::AocError;
+
+// Bags are searched up to this many cubes of each colour, unless told otherwise
+const DEFAULT_LIMIT: usize = 100;
+
+/*
+    The estimate for one colour: the most likely count, and the range of counts the
+    data can't tell apart from it at the confidence level. The upper end is None when
+    counts up to the search limit are all still likely, i.e. the games don't bound it
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorEstimate {
+    pub color: CubeColor,
+    pub estimate: usize,
+    pub lower: usize,
+    pub upper: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BagEstimate {
+    pub colors: Vec<ColorEstimate>,
+    pub confidence: f64,
+    // The natural log of the probability of the games with the estimated bag
+    pub log_likelihood: f64,
+}
+
+impl BagEstimate {
+    // The most likely bag
+    pub fn bag(&self) -> CubeCounts {
+        self.colors
+            .iter()
+            .map(|color| (color.color.clone(), color.estimate))
+            .collect()
+    }
+}
+
+/*
+    Estimates the bag the games were played with, assuming every game was played with the same bag.
+    Each turn draws as many cubes as it shows, without replacement, and puts them back afterwards,
+    so the chance of a turn with a bag is multivariate hypergeometric:
+
+        P(turn) = C(red in bag, red drawn) * C(green in bag, green drawn) * ... / C(cubes in bag, cubes drawn)
+
+    The likelihood of a bag is the product of that over every turn. Only how many turns drew each
+    amount matters, so that's all that's kept of the games. The numerators only depend on the count
+    of their own colour, and the denominator only on the total, which is what makes the most likely
+    bag cheap to find
+*/
+#[derive(Debug, Clone)]
+pub struct BagEstimator {
+    colors: Vec<CubeColor>,
+    // For each colour, how many turns drew each amount of it
+    drawn: Vec<Vec<usize>>,
+    // How many turns drew each amount of cubes, all colours together
+    handfuls: Vec<usize>,
+    limit: usize,
+}
+
+impl BagEstimator {
+    // The colours are the ones that show up in the games
+    pub fn new(conundrum: &Conundrum) -> BagEstimator {
+        BagEstimator::with_palette(conundrum, &conundrum.palette())
+    }
+
+    // Colours of the palette that never show up are taken to be in the bag too
+    pub fn with_palette(conundrum: &Conundrum, palette: &Palette) -> BagEstimator {
+        let colors = palette.colors().to_vec();
+        let mut drawn = vec![vec![]; colors.len()];
+        let mut handfuls = vec![];
+        for turn in conundrum.0.iter().flat_map(|game| game.turns.iter()) {
+            let mut handful = 0;
+            for (color, drawn) in colors.iter().zip(drawn.iter_mut()) {
+                let count = turn.get(color);
+                count_one(drawn, count);
+                handful += count;
+            }
+            count_one(&mut handfuls, handful);
+        }
+        BagEstimator {
+            colors,
+            drawn,
+            handfuls,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    // The most cubes of a single colour a bag can have, it's never below what some turn drew
+    pub fn limit(mut self, limit: usize) -> BagEstimator {
+        self.limit = limit;
+        self
+    }
+
+    // The fewest cubes of each colour a bag needs to have to give every turn
+    fn lower_bounds(&self) -> Vec<usize> {
+        self.drawn
+            .iter()
+            .map(|drawn| drawn.len().saturating_sub(1))
+            .collect()
+    }
+
+    fn limit_of(&self, color: usize) -> usize {
+        self.limit.max(self.lower_bounds()[color])
+    }
+
+    // The numerators of a colour with the given count of it, minus infinity if it can't give the turns
+    fn color_log_likelihood(&self, color: usize, count: usize, ln_factorials: &[f64]) -> f64 {
+        let drawn = &self.drawn[color];
+        if drawn.len() > count + 1 {
+            return f64::NEG_INFINITY;
+        }
+        drawn
+            .iter()
+            .enumerate()
+            .map(|(amount, turns)| *turns as f64 * ln_choose(count, amount, ln_factorials))
+            .sum()
+    }
+
+    // The denominators of a bag with the given amount of cubes, the total is never below a handful
+    fn handful_log_likelihood(&self, total: usize, ln_factorials: &[f64]) -> f64 {
+        self.handfuls
+            .iter()
+            .enumerate()
+            .map(|(amount, turns)| *turns as f64 * ln_choose(total, amount, ln_factorials))
+            .sum()
+    }
+
+    /*
+        The log likelihood of the bag with the given counts of the colours,
+        plus some cubes of colours that never show up
+    */
+    fn log_likelihood_of(&self, counts: &[usize], others: usize, ln_factorials: &[f64]) -> f64 {
+        let total: usize = counts.iter().sum::<usize>() + others;
+        if total < self.handfuls.len().saturating_sub(1) {
+            return f64::NEG_INFINITY;
+        }
+        let colors: f64 = counts
+            .iter()
+            .enumerate()
+            .map(|(color, count)| self.color_log_likelihood(color, *count, ln_factorials))
+            .sum();
+        colors - self.handful_log_likelihood(total, ln_factorials)
+    }
+
+    // The natural log of the probability of the games with the bag, minus infinity if it can't give them
+    pub fn log_likelihood(&self, bag: &CubeCounts) -> f64 {
+        let counts: Vec<usize> = self.colors.iter().map(|color| bag.get(color)).collect();
+        let others = bag
+            .colors()
+            .filter(|color| !self.colors.contains(color))
+            .map(|color| bag.get(color))
+            .sum();
+        let total = counts.iter().sum::<usize>() + others;
+        self.log_likelihood_of(&counts, others, &ln_factorials(total))
+    }
+
+    /*
+        The probability that the bag gave exactly these games, given how many cubes each turn drew.
+        It gets tiny quickly with more games, relative_likelihood is easier to read
+    */
+    pub fn probability(&self, bag: &CubeCounts) -> f64 {
+        self.log_likelihood(bag).exp()
+    }
+
+    // How likely the bag is compared to the most likely one, between 0 and 1
+    pub fn relative_likelihood(&self, bag: &CubeCounts) -> f64 {
+        (self.log_likelihood(bag) - self.estimate().log_likelihood).exp()
+    }
+
+    /*
+        For every total of cubes, the best the given colours can do between them: the sum of their
+        numerators, and the counts giving it, in the order of the colours. Found one colour at a
+        time, as the best split of a total is the best split of what's left after the last colour
+    */
+    fn best_splits(
+        &self,
+        colors: &[usize],
+        ln_factorials: &[f64],
+    ) -> Vec<Option<(f64, Vec<usize>)>> {
+        let lower = self.lower_bounds();
+        let mut splits: Vec<Option<(f64, Vec<usize>)>> = vec![Some((0.0, vec![]))];
+        for &color in colors {
+            let limit = self.limit_of(color);
+            let mut next: Vec<Option<(f64, Vec<usize>)>> = vec![None; splits.len() + limit];
+            for count in lower[color]..=limit {
+                let here = self.color_log_likelihood(color, count, ln_factorials);
+                for (total, split) in splits.iter().enumerate() {
+                    let Some((log_likelihood, counts)) = split else {
+                        continue;
+                    };
+                    let log_likelihood = log_likelihood + here;
+                    if next[total + count]
+                        .as_ref()
+                        .is_none_or(|(best, _)| log_likelihood > *best)
+                    {
+                        let mut counts = counts.clone();
+                        counts.push(count);
+                        next[total + count] = Some((log_likelihood, counts));
+                    }
+                }
+            }
+            splits = next;
+        }
+        splits
+    }
+
+    /*
+        The most likely counts of the given colours when the bag has extra cubes besides them,
+        with their log likelihood. The numerators of the extra cubes are left for the caller
+    */
+    fn best_bag(&self, colors: &[usize], extra: usize, ln_factorials: &[f64]) -> (f64, Vec<usize>) {
+        let mut best = (f64::NEG_INFINITY, self.lower_bounds());
+        for (total, split) in self
+            .best_splits(colors, ln_factorials)
+            .into_iter()
+            .enumerate()
+        {
+            let Some((log_likelihood, counts)) = split else {
+                continue;
+            };
+            let log_likelihood =
+                log_likelihood - self.handful_log_likelihood(total + extra, ln_factorials);
+            if log_likelihood > best.0 {
+                best = (log_likelihood, counts);
+            }
+        }
+        best
+    }
+
+    // The most likely bag, with a 95% confidence interval for each colour
+    pub fn estimate(&self) -> BagEstimate {
+        self.estimate_at(0.95)
+    }
+
+    pub fn estimate_with_confidence(&self, confidence: f64) -> Result<BagEstimate, AocError> {
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(AocError::invalid(
+                DAY,
+                format!("confidence has to be between 0 and 1, got {}", confidence),
+            ));
+        }
+        Ok(self.estimate_at(confidence))
+    }
+
+    /*
+        The interval of a colour has the counts whose profile likelihood (the best the other colours
+        can do with the count fixed) is within half the chi-squared quantile of the best overall
+    */
+    fn estimate_at(&self, confidence: f64) -> BagEstimate {
+        let limits: Vec<usize> = (0..self.colors.len()).map(|c| self.limit_of(c)).collect();
+        let ln_factorials = ln_factorials(limits.iter().sum());
+        let all_colors: Vec<usize> = (0..self.colors.len()).collect();
+        let (log_likelihood, best) = self.best_bag(&all_colors, 0, &ln_factorials);
+        let z = normal_quantile((1.0 + confidence) / 2.0);
+        let threshold = log_likelihood - z * z / 2.0;
+
+        let lower_bounds = self.lower_bounds();
+        let colors = self
+            .colors
+            .iter()
+            .enumerate()
+            .map(|(color, name)| {
+                let others: Vec<usize> =
+                    all_colors.iter().copied().filter(|c| *c != color).collect();
+                let likely = |count: usize| {
+                    let (others_best, _) = self.best_bag(&others, count, &ln_factorials);
+                    let here = self.color_log_likelihood(color, count, &ln_factorials);
+                    others_best + here >= threshold
+                };
+                let lower = (lower_bounds[color]..best[color])
+                    .rev()
+                    .take_while(|count| likely(*count))
+                    .last()
+                    .unwrap_or(best[color]);
+                let upper = (best[color] + 1..=limits[color])
+                    .take_while(|count| likely(*count))
+                    .last()
+                    .unwrap_or(best[color]);
+                ColorEstimate {
+                    color: name.clone(),
+                    estimate: best[color],
+                    lower,
+                    upper: (upper < limits[color]).then_some(upper),
+                }
+            })
+            .collect();
+        BagEstimate {
+            colors,
+            confidence,
+            log_likelihood,
+        }
+    }
+}
+
+fn ln_choose(n: usize, k: usize, ln_factorials: &[f64]) -> f64 {
+    ln_factorials[n] - ln_factorials[k] - ln_factorials[n - k]
+}
+
+fn count_one(counts: &mut Vec<usize>, amount: usize) {
+    if counts.len() <= amount {
+        counts.resize(amount + 1, 0);
+    }
+    counts[amount] += 1;
+}
+
+// ln(n!) for every n up to the given one
+fn ln_factorials(up_to: usize) -> Vec<f64> {
+    let mut ln_factorials = Vec::with_capacity(up_to + 1);
+    ln_factorials.push(0.0);
+    for n in 1..=up_to {
+        ln_factorials.push(ln_factorials[n - 1] + (n as f64).ln());
+    }
+    ln_factorials
+}
+
+// The complementary error function, to about 1e-7 (Numerical Recipes' erfcc)
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let polynomial = [
+        -1.265_512_23,
+        1.000_023_68,
+        0.374_091_96,
+        0.096_784_18,
+        -0.186_288_06,
+        0.278_868_07,
+        -1.135_203_98,
+        1.488_515_87,
+        -0.822_152_23,
+        0.170_872_77,
+    ]
+    .iter()
+    .rev()
+    .fold(0.0, |sum, coefficient| coefficient + t * sum);
+    let erfc = t * (-x * x + polynomial).exp();
+    if x >= 0.0 {
+        erfc
+    } else {
+        2.0 - erfc
+    }
+}
+
+// The z of the standard normal distribution with the given probability below it, found by bisection
+fn normal_quantile(probability: f64) -> f64 {
+    let cdf = |z: f64| 1.0 - 0.5 * erfc(z / std::f64::consts::SQRT_2);
+    let (mut low, mut high) = (-10.0, 10.0);
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if cdf(middle) < probability {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
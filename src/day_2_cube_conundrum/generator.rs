@@ -0,0 +1,170 @@
+use crate::day_2_cube_conundrum::{Conundrum, CubeColor, CubeCounts, Game, DAY};
+use crate::error::{AocError, ErrorKind};
+use std::ops::RangeInclusive;
+
+/*
+    A small splitmix64 generator. The logs only have to look random and come out
+    the same for the same seed, on every platform and version of the crate
+*/
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // A number in 0..n, n has to be above 0
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn in_range(&mut self, range: &RangeInclusive<usize>) -> usize {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+}
+
+/*
+    A generated log, with which of its games are possible with the bag it was generated from
+*/
+#[derive(Debug, PartialEq, Eq)]
+pub struct GeneratedLog {
+    pub conundrum: Conundrum,
+    pub possible: Vec<usize>,
+}
+
+impl GeneratedLog {
+    pub fn sum_of_possible_game_ids(&self) -> usize {
+        self.possible.iter().sum()
+    }
+}
+
+/*
+    Makes up game logs for a known bag. Every turn draws a handful of cubes from the bag
+    without replacement, and puts them back afterwards. Games meant to be impossible get
+    one turn that shows more cubes of a colour than the bag has
+*/
+#[derive(Debug, Clone)]
+pub struct GameGenerator {
+    bag: Vec<(CubeColor, usize)>,
+    turns: RangeInclusive<usize>,
+    largest_handful: usize,
+    impossible_share: f64,
+    rng: Rng,
+}
+
+impl GameGenerator {
+    // Games of 1 to 6 turns of up to the whole bag, a third of them impossible
+    pub fn new(bag: &CubeCounts, seed: u64) -> Result<GameGenerator, AocError> {
+        let mut bag: Vec<(CubeColor, usize)> = bag
+            .colors()
+            .map(|color| (color.clone(), bag.get(color)))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        if bag.is_empty() {
+            return Err(AocError::day(DAY, ErrorKind::Empty { what: "bag" }));
+        }
+        // The map of the bag has no order of its own, and the same seed has to give the same log
+        bag.sort();
+        let total = bag.iter().map(|(_, count)| count).sum();
+        Ok(GameGenerator {
+            bag,
+            turns: 1..=6,
+            largest_handful: total,
+            impossible_share: 1.0 / 3.0,
+            rng: Rng(seed),
+        })
+    }
+
+    // How many turns a game can have, every game has at least one
+    pub fn turns(mut self, turns: RangeInclusive<usize>) -> GameGenerator {
+        let start = (*turns.start()).max(1);
+        self.turns = start..=(*turns.end()).max(start);
+        self
+    }
+
+    /*
+        The most cubes a turn draws, at least 1 and at most the whole bag. Every turn drawing the
+        whole bag gives it away, small handfuls leave more to guess
+    */
+    pub fn largest_handful(mut self, cubes: usize) -> GameGenerator {
+        let total = self.bag.iter().map(|(_, count)| count).sum();
+        self.largest_handful = cubes.clamp(1, total);
+        self
+    }
+
+    // The share of games that can't be played with the bag, between 0 and 1
+    pub fn impossible_share(mut self, share: f64) -> GameGenerator {
+        self.impossible_share = share.clamp(0.0, 1.0);
+        self
+    }
+
+    // Draws a handful of cubes from the bag, one at a time
+    fn draw(&mut self) -> Vec<(CubeColor, usize)> {
+        let mut left: Vec<usize> = self.bag.iter().map(|(_, count)| *count).collect();
+        let total: usize = left.iter().sum();
+        let mut drawn = vec![0; left.len()];
+        let handful = self.rng.in_range(&(1..=self.largest_handful));
+        for remaining in (total - handful + 1..=total).rev() {
+            let mut cube = self.rng.below(remaining);
+            let color = left
+                .iter()
+                .position(|&count| {
+                    let here = cube < count;
+                    cube = cube.saturating_sub(count);
+                    here
+                })
+                .unwrap();
+            left[color] -= 1;
+            drawn[color] += 1;
+        }
+        self.bag
+            .iter()
+            .zip(drawn)
+            .filter(|(_, drawn)| *drawn > 0)
+            .map(|((color, _), drawn)| (color.clone(), drawn))
+            .collect()
+    }
+
+    // A game that can be played with the bag, or one that can't
+    pub fn game(&mut self, id: usize, possible: bool) -> Game {
+        let turn_count = self.rng.in_range(&self.turns.clone());
+        let mut turns: Vec<Vec<(CubeColor, usize)>> =
+            (0..turn_count).map(|_| self.draw()).collect();
+        if !possible {
+            let turn = self.rng.below(turns.len());
+            let color = self.rng.below(self.bag.len());
+            let (color, in_bag) = self.bag[color].clone();
+            let too_many = in_bag + 1 + self.rng.below(3);
+            turns[turn].retain(|(drawn, _)| *drawn != color);
+            turns[turn].push((color, too_many));
+        }
+        Game {
+            id,
+            turns: turns.into_iter().map(CubeCounts::from_iter).collect(),
+        }
+    }
+
+    // A log of games numbered from 1
+    pub fn generate(&mut self, games: usize) -> GeneratedLog {
+        let mut possible = vec![];
+        let games = (1..=games)
+            .map(|id| {
+                let is_possible = (self.rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+                    >= self.impossible_share;
+                if is_possible {
+                    possible.push(id);
+                }
+                self.game(id, is_possible)
+            })
+            .collect();
+        GeneratedLog {
+            conundrum: Conundrum(games),
+            possible,
+        }
+    }
+}
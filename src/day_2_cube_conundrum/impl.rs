@@ -1,18 +1,19 @@
 use crate::day_2_cube_conundrum;
-use crate::day_2_cube_conundrum::{Conundrum, CubeColor, CubeCounts, Game};
-use std::string::String;
+use crate::day_2_cube_conundrum::{Conundrum, CubeColor, CubeCounts, Game, Palette, DAY};
+use crate::error::{AocError, ErrorKind};
+use crate::solution::{Answer, Solution};
 
 use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
-use strum::IntoEnumIterator;
+use std::sync::LazyLock;
+
+// The colours of the puzzle, the only ones parsing accepts unless told otherwise
+static STANDARD: LazyLock<Palette> = LazyLock::new(Palette::standard);
 
 impl FromIterator<(CubeColor, usize)> for CubeCounts {
     fn from_iter<T: IntoIterator<Item = (CubeColor, usize)>>(iter: T) -> Self {
-        let the_hashmap: HashMap<CubeColor, usize> = CubeColor::iter()
-            .map(|variant| (variant, 0)) // Set default values to 0
-            .chain(iter) // Combine with the given
-            .collect();
+        let the_hashmap: HashMap<CubeColor, usize> = iter.into_iter().collect();
         return CubeCounts { 0: the_hashmap };
     }
 }
@@ -23,72 +24,255 @@ impl<const N: usize> From<[(CubeColor, usize); N]> for CubeCounts {
     }
 }
 
+impl PartialEq for CubeCounts {
+    fn eq(&self, other: &Self) -> bool {
+        self.colors()
+            .chain(other.colors())
+            .all(|color| self.get(color) == other.get(color))
+    }
+}
+
+impl Eq for CubeCounts {}
+
+// Colours are written the way the games write them, e.g. "red"
 impl fmt::Display for CubeColor {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/*
+    Writes the counts the way a turn is written, e.g. "4 red, 3 blue".
+    The colours go in the order of CubeColor, whatever order they were parsed in
+*/
+impl fmt::Display for CubeCounts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut counts: Vec<(&CubeColor, &usize)> = self.0.iter().collect();
+        counts.sort();
+        for (i, (color, count)) in counts.into_iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{} {}", count, color)?;
+        }
+        Ok(())
+    }
+}
+
+// Writes the game back as a line of the input, e.g. "Game 1: 4 red, 3 blue; 2 green"
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+        for (i, turn) in self.turns.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", turn)?;
+        }
+        Ok(())
+    }
+}
+
+// One game per line, each ending in a newline like the input does
+impl fmt::Display for Conundrum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for game in &self.0 {
+            writeln!(f, "{}", game)?;
+        }
+        Ok(())
+    }
+}
+
+impl CubeColor {
+    // The lowercase name of the colour, the way it's written in the games
+    pub fn name(&self) -> &str {
         match self {
-            CubeColor::Red => write!(f, "Red"),
-            CubeColor::Green => write!(f, "Green"),
-            CubeColor::Blue => write!(f, "Blue"),
+            CubeColor::Red => "red",
+            CubeColor::Green => "green",
+            CubeColor::Blue => "blue",
+            CubeColor::Named(name) => name,
         }
     }
 }
 
+/*
+    Any single word is a colour, whatever its case.
+    Red, green and blue always come back as their own variant, never as Named
+*/
 impl FromStr for CubeColor {
-    type Err = &'static str;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        const ERROR_STR: &str = "Unable to obtain a color from the given string";
         let clean_input = s.trim().to_lowercase();
-        let mapping: HashMap<String, Self> = Self::iter()
-            .map(|variant| (variant.to_string().to_lowercase(), variant))
-            .collect();
-        mapping.get(clean_input.as_str()).cloned().ok_or(ERROR_STR)
+        if clean_input.is_empty() {
+            return Err(AocError::day(DAY, ErrorKind::Empty { what: "cube color" }));
+        }
+        if !clean_input.chars().all(char::is_alphabetic) {
+            return Err(AocError::day(
+                DAY,
+                ErrorKind::UnknownToken {
+                    what: "cube color",
+                    token: s.trim().to_string(),
+                },
+            ));
+        }
+        Ok(match clean_input.as_str() {
+            "red" => CubeColor::Red,
+            "green" => CubeColor::Green,
+            "blue" => CubeColor::Blue,
+            _ => CubeColor::Named(clean_input),
+        })
+    }
+}
+
+impl Palette {
+    pub fn standard() -> Palette {
+        Palette(vec![CubeColor::Red, CubeColor::Green, CubeColor::Blue])
+    }
+
+    // The colours in the order given, without repeats
+    pub fn new(colors: impl IntoIterator<Item = CubeColor>) -> Palette {
+        let mut palette = Palette(vec![]);
+        for color in colors {
+            if !palette.contains(&color) {
+                palette.0.push(color);
+            }
+        }
+        palette
+    }
+
+    pub fn colors(&self) -> &[CubeColor] {
+        &self.0
+    }
+
+    pub fn contains(&self, color: &CubeColor) -> bool {
+        self.0.contains(color)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/*
+    Reads a list of colours, e.g. "red, green, blue, yellow"
+*/
+impl FromStr for Palette {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let colors = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(CubeColor::from_str)
+            .collect::<Result<Vec<CubeColor>, AocError>>()?;
+        Ok(Palette::new(colors))
     }
 }
 
 impl CubeCounts {
     pub fn get(&self, color: &CubeColor) -> usize {
-        self.0.get(&color).unwrap().clone()
+        self.0.get(color).copied().unwrap_or_default()
+    }
+
+    // The colours there are counts for, zeros included
+    pub fn colors(&self) -> impl Iterator<Item = &CubeColor> {
+        self.0.keys()
     }
+
     /*
         Whether the given cube count fits the other one
     */
     pub fn fits(&self, bag_other: &Self) -> bool {
-        CubeColor::iter().all(|color| self.0[&color] <= bag_other.0[&color])
+        self.0
+            .iter()
+            .all(|(color, count)| *count <= bag_other.get(color))
     }
     /*
         Takes in a line like " 3 blue, 4 red",
         and returns a Turn with the correct fields
     */
-    pub fn from_turn_str(turn_string: &str) -> Result<CubeCounts, String> {
+    pub fn from_turn_str(turn_string: &str) -> Result<CubeCounts, AocError> {
+        CubeCounts::parse_turn(turn_string, Some(&STANDARD))
+    }
+
+    // Same as from_turn_str, with the colours of the palette, or any colour without one
+    pub fn from_turn_str_in(
+        turn_string: &str,
+        palette: Option<&Palette>,
+    ) -> Result<CubeCounts, AocError> {
+        CubeCounts::parse_turn(turn_string, palette)
+    }
+
+    fn parse_turn(turn_string: &str, palette: Option<&Palette>) -> Result<CubeCounts, AocError> {
         let turn_vector: Vec<(CubeColor, usize)> = turn_string
             .trim()
             .split(',')
-            .map(day_2_cube_conundrum::get_elements_from_turn_token)
-            .collect::<Option<Vec<(CubeColor, usize)>>>()
-            .ok_or(String::from("Unable to parse turn string"))?;
+            .map(|token| day_2_cube_conundrum::get_elements_from_turn_token(token, palette))
+            .collect::<Result<Vec<(CubeColor, usize)>, AocError>>()?;
         return Ok(CubeCounts::from_iter(turn_vector.into_iter()));
     }
 
+    // The product of the colours there are counts for
     pub fn power(&self) -> usize {
-        CubeColor::iter().map(|color| self.get(&color)).product()
+        self.0.values().product()
+    }
+
+    // The product over every colour of the palette, so a colour that never showed up makes it 0
+    pub fn power_in(&self, palette: &Palette) -> usize {
+        palette
+            .colors()
+            .iter()
+            .map(|color| self.get(color))
+            .product()
     }
 }
 
 impl FromStr for Conundrum {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            0: s.trim()
-                .split("\n")
-                .map(Game::from_str)
-                .collect::<Result<Vec<Game>, _>>()?,
-        })
+        Conundrum::parse(s, Some(&STANDARD))
     }
 }
 
 impl Conundrum {
+    // Parses the games, failing on any colour that isn't in the palette
+    pub fn parse_in(s: &str, palette: &Palette) -> Result<Conundrum, AocError> {
+        Conundrum::parse(s, Some(palette))
+    }
+
+    // Parses the games with whatever colours they have, see palette for which ones those are
+    pub fn parse_any(s: &str) -> Result<Conundrum, AocError> {
+        Conundrum::parse(s, None)
+    }
+
+    fn parse(s: &str, palette: Option<&Palette>) -> Result<Conundrum, AocError> {
+        let games = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_n, line)| Game::parse(line, palette).map_err(|e| e.at_line(line_n + 1)))
+            .collect::<Result<Vec<Game>, AocError>>()?;
+        Ok(Conundrum(games))
+    }
+
+    // Every colour that shows up in the games, in the order of CubeColor
+    pub fn palette(&self) -> Palette {
+        let mut colors: Vec<CubeColor> = self
+            .0
+            .iter()
+            .flat_map(|game| game.turns.iter())
+            .flat_map(|turn| turn.colors().cloned())
+            .collect();
+        colors.sort();
+        Palette::new(colors)
+    }
+
     pub fn possible_games(&self, bag: &CubeCounts) -> Vec<usize> {
         self.0
             .iter()
@@ -105,10 +289,15 @@ impl Conundrum {
         self.0.iter().map(Game::smallest_bag).collect()
     }
 
+    // The powers over the colours that show up in the games
     pub fn powers(&self) -> Vec<usize> {
+        self.powers_in(&self.palette())
+    }
+
+    pub fn powers_in(&self, palette: &Palette) -> Vec<usize> {
         self.smallest_bags_needed()
             .iter()
-            .map(CubeCounts::power)
+            .map(|bag| bag.power_in(palette))
             .collect()
     }
 }
@@ -122,9 +311,12 @@ impl Game {
         self.turns.iter().all(|turn| turn.fits(&bag))
     }
 
+    // The most cubes of each colour that were drawn at once
     pub fn smallest_bag(&self) -> CubeCounts {
-        CubeColor::iter()
-            .map(|color| (color.clone(), self.highest_count(&color)))
+        let colors: Vec<&CubeColor> = self.turns.iter().flat_map(CubeCounts::colors).collect();
+        colors
+            .into_iter()
+            .map(|color| (color.clone(), self.highest_count(color)))
             .collect()
     }
 
@@ -141,23 +333,58 @@ impl Game {
 }
 
 impl FromStr for Game {
-    type Err = String;
-    fn from_str(game_string: &str) -> Result<Self, String> {
+    type Err = AocError;
+    fn from_str(game_string: &str) -> Result<Self, AocError> {
+        Game::parse(game_string, Some(&STANDARD))
+    }
+}
+
+impl Game {
+    fn parse(game_string: &str, palette: Option<&Palette>) -> Result<Game, AocError> {
         let right_string = game_string.trim().replace("Game", "");
-        let (game_id_token, turn_tokens) = right_string
-            .trim()
-            .split_once(':')
-            .ok_or("Unable to parse game str due to missing colon".to_string())?;
-        let game_id: usize = game_id_token
-            .parse()
-            .or(Err("Unable to parse game id".to_string()))?;
+        let (game_id_token, turn_tokens) =
+            right_string.trim().split_once(':').ok_or(AocError::day(
+                DAY,
+                ErrorKind::MissingDelimiter {
+                    what: "game",
+                    delimiter: ":",
+                },
+            ))?;
+        let game_id: usize = game_id_token.trim().parse().map_err(|_| {
+            AocError::day(
+                DAY,
+                ErrorKind::InvalidNumber {
+                    what: "game id",
+                    token: game_id_token.trim().to_string(),
+                },
+            )
+        })?;
         let turn_cube_counts: Vec<CubeCounts> = turn_tokens
             .split(";")
-            .map(|turn_token| CubeCounts::from_turn_str(turn_token))
-            .collect::<Result<Vec<CubeCounts>, String>>()?;
+            .map(|turn_token| CubeCounts::parse_turn(turn_token, palette))
+            .collect::<Result<Vec<CubeCounts>, AocError>>()?;
         Ok(Self {
             id: game_id,
             turns: turn_cube_counts,
         })
     }
 }
+
+impl Solution for Conundrum {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        let bag = CubeCounts::from([
+            (CubeColor::Red, 12),
+            (CubeColor::Green, 13),
+            (CubeColor::Blue, 14),
+        ]);
+        Ok(Box::new(self.sum_of_possible_game_ids(&bag)))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.powers().iter().sum::<usize>()))
+    }
+}
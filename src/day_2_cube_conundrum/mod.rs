@@ -1,24 +1,74 @@
+use crate::error::{AocError, ErrorKind};
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::str::FromStr;
-use strum_macros::EnumIter;
 
+mod estimate;
+mod generator;
 mod r#impl;
 
-#[derive(Debug, Eq, PartialEq, Hash, EnumIter, Clone)]
+pub use estimate::{BagEstimate, BagEstimator, ColorEstimate};
+pub use generator::{GameGenerator, GeneratedLog};
+
+const DAY: usize = 2;
+
+/*
+    The colour of a cube. The three colours of the puzzle have a variant of their own,
+    any other colour goes by its lowercase name
+*/
+#[derive(Debug, Eq, PartialEq, Hash, Clone, PartialOrd, Ord)]
 pub enum CubeColor {
     Red,
     Blue,
     Green,
+    Named(String),
 }
-#[derive(Eq, Debug, PartialEq)]
+
+/*
+    The colours cubes can have. Declared up front, it rejects any other colour while parsing,
+    otherwise it can be discovered from the games themselves
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette(Vec<CubeColor>);
+
+/*
+    How many cubes of each colour. A colour that isn't there counts as 0 cubes,
+    so counts with and without explicit zeros are the same
+*/
+#[derive(Debug)]
 pub struct CubeCounts(HashMap<CubeColor, usize>);
 
-fn get_elements_from_turn_token(token: &str) -> Option<(CubeColor, usize)> {
-    let (number_token, color_token) = token.trim().split_once(' ')?;
-    let color = CubeColor::from_str(color_token).ok()?;
-    let count = usize::from_str(number_token).ok()?;
-    Some((color, count))
+fn get_elements_from_turn_token(
+    token: &str,
+    palette: Option<&Palette>,
+) -> Result<(CubeColor, usize), AocError> {
+    let (number_token, color_token) = token.trim().split_once(' ').ok_or(AocError::day(
+        DAY,
+        ErrorKind::MissingDelimiter {
+            what: "cube count",
+            delimiter: " ",
+        },
+    ))?;
+    let color = CubeColor::from_str(color_token)?;
+    if palette.is_some_and(|palette| !palette.contains(&color)) {
+        return Err(AocError::day(
+            DAY,
+            ErrorKind::UnknownToken {
+                what: "cube color",
+                token: color_token.trim().to_string(),
+            },
+        ));
+    }
+    let count = usize::from_str(number_token).map_err(|_| {
+        AocError::day(
+            DAY,
+            ErrorKind::InvalidNumber {
+                what: "cube count",
+                token: number_token.to_string(),
+            },
+        )
+    })?;
+    Ok((color, count))
 }
 
 #[derive(Eq, PartialEq, Debug)]
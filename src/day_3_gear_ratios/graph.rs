@@ -0,0 +1,125 @@
+use crate::day_3_gear_ratios::{checked_sum, CharInfo, PartNumber, SchematicIndex};
+use crate::error::AocError;
+
+/*
+    Symbols and the part numbers joining them, one of the connected pieces of the schematic.
+    Both are in reading order
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine<'a> {
+    pub symbols: Vec<&'a CharInfo>,
+    pub numbers: Vec<&'a PartNumber>,
+}
+
+impl Machine<'_> {
+    pub fn sum(&self) -> Result<usize, AocError> {
+        checked_sum(
+            self.numbers.iter().map(|number| number.number),
+            "sum of a machine",
+        )
+    }
+}
+
+/*
+    The schematic as a bipartite graph, with an edge between every symbol and every number next to it.
+    A machine is everything connected through those edges, so two symbols sharing a number are in the same one
+*/
+#[derive(Debug, Clone)]
+pub struct SchematicGraph<'a> {
+    index: &'a SchematicIndex,
+    // The symbols and numbers of each machine, as places in the index
+    machines: Vec<(Vec<usize>, Vec<usize>)>,
+}
+
+impl<'a> SchematicGraph<'a> {
+    pub fn new(index: &'a SchematicIndex) -> SchematicGraph<'a> {
+        let mut symbol_seen = vec![false; index.symbols().len()];
+        let mut number_seen = vec![false; index.numbers().len()];
+        let mut machines = vec![];
+        for first in 0..index.symbols().len() {
+            if symbol_seen[first] {
+                continue;
+            }
+            symbol_seen[first] = true;
+            let (mut symbols, mut numbers) = (vec![], vec![]);
+            let mut stack = vec![first];
+            while let Some(symbol) = stack.pop() {
+                symbols.push(symbol);
+                for &number in index.number_links(symbol) {
+                    if number_seen[number] {
+                        continue;
+                    }
+                    number_seen[number] = true;
+                    numbers.push(number);
+                    for &other in index.symbol_links(number) {
+                        if !symbol_seen[other] {
+                            symbol_seen[other] = true;
+                            stack.push(other);
+                        }
+                    }
+                }
+            }
+            symbols.sort_unstable();
+            numbers.sort_unstable();
+            machines.push((symbols, numbers));
+        }
+        SchematicGraph { index, machines }
+    }
+
+    // Every (symbol, number) pair next to each other, by the symbol's place in reading order
+    pub fn edges(&self) -> impl Iterator<Item = (&'a CharInfo, &'a PartNumber)> + '_ {
+        let index = self.index;
+        index
+            .symbols()
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, symbol)| {
+                index.numbers_next_to(i).map(move |number| (symbol, number))
+            })
+    }
+
+    fn machine(&self, (symbols, numbers): &(Vec<usize>, Vec<usize>)) -> Machine<'a> {
+        Machine {
+            symbols: symbols.iter().map(|&s| &self.index.symbols()[s]).collect(),
+            numbers: numbers.iter().map(|&n| &self.index.numbers()[n]).collect(),
+        }
+    }
+
+    // The connected components with at least one symbol, by where their first symbol is
+    pub fn machines(&self) -> Vec<Machine<'a>> {
+        self.machines
+            .iter()
+            .map(|machine| self.machine(machine))
+            .collect()
+    }
+
+    // The machine with the biggest sum of part numbers, the first one read if several tie
+    pub fn largest_machine(&self) -> Result<Option<Machine<'a>>, AocError> {
+        let mut largest: Option<(usize, Machine<'a>)> = None;
+        for machine in self.machines() {
+            let sum = machine.sum()?;
+            if largest.as_ref().is_none_or(|(most, _)| sum > *most) {
+                largest = Some((sum, machine));
+            }
+        }
+        Ok(largest.map(|(_, machine)| machine))
+    }
+
+    // The numbers next to more than one symbol, in reading order
+    pub fn shared_numbers(&self) -> Vec<&'a PartNumber> {
+        let index = self.index;
+        (0..index.numbers().len())
+            .filter(|&n| index.symbol_links(n).len() > 1)
+            .map(|n| &index.numbers()[n])
+            .collect()
+    }
+
+    // The numbers next to no symbol at all, in reading order
+    pub fn orphans(&self) -> Vec<&'a PartNumber> {
+        let index = self.index;
+        (0..index.numbers().len())
+            .filter(|&n| index.symbol_links(n).is_empty())
+            .map(|n| &index.numbers()[n])
+            .collect()
+    }
+}
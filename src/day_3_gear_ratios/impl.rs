@@ -1,36 +1,42 @@
-use crate::day_3_gear_ratios;
-use crate::day_3_gear_ratios::{CharInfo, PartNumber, Schematic, Symbol};
+use crate::day_3_gear_ratios::checked_sum;
+use crate::day_3_gear_ratios::{
+    Adjacency, CharInfo, PartNumber, Schematic, SchematicGraph, SchematicIndex, SchematicRenderer,
+    SchematicRules, Symbol, DAY,
+};
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
-use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 impl Symbol {
-    fn from(char_info: CharInfo) -> Option<Symbol> {
-        if day_3_gear_ratios::is_symbol(&char_info.character) {
+    fn from(char_info: CharInfo, rules: &SchematicRules) -> Option<Symbol> {
+        if rules.is_symbol(char_info.character) {
             return Some(Symbol { 0: char_info });
         }
         return None;
     }
+}
 
-    fn char(&self) -> &char {
-        &self.0.character
-    }
-    fn row_adjacent_index_iter(&self) -> RangeInclusive<i32> {
-        (self.0.row as i32 - 1..=self.0.row as i32 + 1).into_iter()
+impl Schematic {
+    // Reads the schematic with other symbols, gears or adjacency than the puzzle's
+    pub fn parse_with(s: &str, rules: &SchematicRules) -> Result<Schematic, AocError> {
+        rules.validate()?;
+        let chars = Grid::parse(s, "schematic", Some).map_err(|e| e.in_day(DAY))?;
+        let index = SchematicIndex::new(&chars, rules)?;
+        Ok(Self { chars, index })
     }
 
-    fn column_adjacent_index_iter(&self) -> RangeInclusive<i32> {
-        (self.0.column as i32 - 1..=self.0.column as i32 + 1).into_iter()
+    pub fn rules(&self) -> &SchematicRules {
+        self.index.rules()
     }
-}
 
-impl Schematic {
     pub fn get(&self, x: usize, y: usize) -> Option<&char> {
-        self.chars.get(y)?.get(x)
+        self.chars.get(y, x)
     }
 
     pub fn get_number_row_boundaries(&self, row: usize, column: usize) -> Option<PartNumber> {
-        let the_row_chars = self.chars.get(row)?;
+        let the_row_chars = self.chars.row(row)?;
 
         if !the_row_chars.get(column)?.is_digit(10) {
             return None;
@@ -63,44 +69,57 @@ impl Schematic {
         })
     }
     pub fn get_numbers_adjacent_to_symbol(&self, symbol: &Symbol) -> Vec<PartNumber> {
-        let row_iter = symbol.row_adjacent_index_iter().into_iter();
-        let column_iter = symbol.column_adjacent_index_iter().into_iter();
-        row_iter
-            .cartesian_product(column_iter)
-            .map(|(row, column)| self.get_number_row_boundaries(row as usize, column as usize))
+        let (row, column) = (symbol.0.row, symbol.0.column);
+        let neighbours: Vec<(usize, usize)> = match self.rules().get_adjacency() {
+            Adjacency::Eight => self.chars.neighbours8(row, column).collect(),
+            Adjacency::Four => self.chars.neighbours4(row, column).collect(),
+        };
+        neighbours
+            .into_iter()
+            .map(|(row, column)| self.get_number_row_boundaries(row, column))
             .flatten()
             .unique()
             .collect()
     }
 
-    pub fn get_symbols(&self) -> Vec<Symbol> {
-        let row_indices = 0..self.height;
-        let column_indices = 0..self.width;
-        let row_column_iterator = row_indices.cartesian_product(column_indices);
-        let char_infos = row_column_iterator.map(|(row, column)| CharInfo {
-            character: self.chars[row][column],
-            row,
-            column,
-        });
-        char_infos.map(Symbol::from).flatten().collect()
+    // The symbols and numbers as a graph, to find the machines they make up
+    pub fn graph(&self) -> SchematicGraph<'_> {
+        SchematicGraph::new(&self.index)
     }
-    pub fn get_gear_ratios(&self) -> Vec<usize> {
-        self.get_symbols()
+
+    pub fn get_symbols(&self) -> Vec<Symbol> {
+        let char_infos = self
+            .chars
             .iter()
-            .filter(|&symbol| *symbol.char() == '*')
-            .map(|symbol| self.get_numbers_adjacent_to_symbol(symbol))
-            .filter(|vector| vector.len() == 2)
-            .map(|vector| vector.get(0).unwrap() * vector.get(1).unwrap())
+            .map(|((row, column), &character)| CharInfo {
+                character,
+                row,
+                column,
+            });
+        char_infos
+            .filter_map(|char_info| Symbol::from(char_info, self.rules()))
             .collect()
     }
+    pub fn index(&self) -> &SchematicIndex {
+        &self.index
+    }
+
+    pub fn chars(&self) -> &Grid<char> {
+        &self.chars
+    }
+
+    pub fn renderer(&self) -> SchematicRenderer<'_> {
+        SchematicRenderer::new(self)
+    }
+
+    pub fn get_gear_ratios(&self) -> Result<Vec<usize>, AocError> {
+        self.index.gear_ratios().collect()
+    }
+
+    // In reading order, a number that shows up twice in the schematic counts twice
     pub fn get_part_numbers(&self) -> Vec<usize> {
-        self.get_symbols()
-            .into_iter()
-            .map(|symbol| self.get_numbers_adjacent_to_symbol(&symbol))
-            .collect::<Vec<Vec<PartNumber>>>()
-            .concat()
-            .iter()
-            .unique()
+        self.index
+            .part_numbers()
             .map(|part_number| part_number.number)
             .collect()
     }
@@ -114,26 +133,29 @@ impl std::ops::Mul<&PartNumber> for &PartNumber {
     }
 }
 impl FromStr for Schematic {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let chars: Vec<Vec<char>> = s
-            .trim()
-            .lines()
-            .map(|line| line.trim().chars().collect())
-            .collect();
-        let height = chars.len();
-        let width = chars.iter().next().unwrap().len();
-
-        if !chars.iter().all(|row| row.len() == width) {
-            return Err(
-                "Unable to create schematic since all rows are not equal length".to_string(),
-            );
-        }
-        Ok(Self {
-            chars,
-            width,
-            height,
-        })
+        Schematic::parse_with(s, &SchematicRules::default())
+    }
+}
+
+impl Solution for Schematic {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(checked_sum(
+            self.get_part_numbers(),
+            "sum of part numbers",
+        )?))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(checked_sum(
+            self.get_gear_ratios()?,
+            "sum of gear ratios",
+        )?))
     }
 }
@@ -0,0 +1,187 @@
+use crate::day_3_gear_ratios::{Adjacency, CharInfo, PartNumber, SchematicRules, DAY};
+use crate::error::{AocError, ErrorKind};
+use crate::grid::Grid;
+use smallvec::SmallVec;
+
+// A symbol touches at most two numbers per row, and most numbers touch a single symbol
+type Links = SmallVec<[usize; 2]>;
+
+/*
+    Every number and symbol of the schematic, found in a single pass over it, and which of them
+    are next to each other. Both are kept in reading order, and everything takes time and space
+    linear in the size of the schematic, however wide it gets
+*/
+#[derive(Debug, Clone, Default)]
+pub struct SchematicIndex {
+    numbers: Vec<PartNumber>,
+    symbols: Vec<CharInfo>,
+    // Where the numbers and symbols of each row start in the lists above
+    row_numbers: Vec<usize>,
+    row_symbols: Vec<usize>,
+    symbol_numbers: Vec<Links>,
+    number_symbols: Vec<Links>,
+    rules: SchematicRules,
+}
+
+impl SchematicIndex {
+    pub fn new(chars: &Grid<char>, rules: &SchematicRules) -> Result<SchematicIndex, AocError> {
+        let mut index = SchematicIndex {
+            rules: rules.clone(),
+            ..SchematicIndex::default()
+        };
+        for (row, row_chars) in chars.rows().enumerate() {
+            index.row_numbers.push(index.numbers.len());
+            index.row_symbols.push(index.symbols.len());
+            index.tokenize_row(row, row_chars)?;
+        }
+        index.row_numbers.push(index.numbers.len());
+        index.row_symbols.push(index.symbols.len());
+
+        index.symbol_numbers = vec![Links::new(); index.symbols.len()];
+        index.number_symbols = vec![Links::new(); index.numbers.len()];
+        for row in 0..chars.height() {
+            for number_row in row.saturating_sub(1)..(row + 2).min(chars.height()) {
+                index.link_rows(row, number_row);
+            }
+        }
+        Ok(index)
+    }
+
+    fn tokenize_row(&mut self, row: usize, row_chars: &[char]) -> Result<(), AocError> {
+        let mut number: Option<PartNumber> = None;
+        for (column, &character) in row_chars.iter().enumerate() {
+            if let Some(digit) = character.to_digit(10) {
+                let part_number = number.get_or_insert(PartNumber {
+                    number: 0,
+                    row,
+                    column_start: column,
+                    column_end: column,
+                });
+                part_number.column_end = column;
+                part_number.number = part_number
+                    .number
+                    .checked_mul(10)
+                    .and_then(|number| number.checked_add(digit as usize))
+                    .ok_or_else(|| {
+                        AocError::day(
+                            DAY,
+                            ErrorKind::InvalidNumber {
+                                what: "part number",
+                                token: row_chars[part_number.column_start..]
+                                    .iter()
+                                    .take_while(|c| c.is_ascii_digit())
+                                    .collect(),
+                            },
+                        )
+                        .at_line(row + 1)
+                        .at_column(part_number.column_start + 1)
+                    })?;
+                continue;
+            }
+            self.numbers.extend(number.take());
+            if self.rules.is_symbol(character) {
+                self.symbols.push(CharInfo {
+                    character,
+                    row,
+                    column,
+                });
+            }
+        }
+        self.numbers.extend(number);
+        Ok(())
+    }
+
+    /*
+        Links the symbols of a row to the numbers of a row next to it (or the same one).
+        Both are sorted by column, so the numbers a symbol could touch only move forward.
+        Without diagonals, a number on another row has to be right above or below the symbol
+    */
+    fn link_rows(&mut self, symbol_row: usize, number_row: usize) {
+        let reach = match self.rules.get_adjacency() {
+            Adjacency::Four if symbol_row != number_row => 0,
+            _ => 1,
+        };
+        let numbers = self.row_numbers[number_row]..self.row_numbers[number_row + 1];
+        let mut first_number = numbers.start;
+        for symbol in self.row_symbols[symbol_row]..self.row_symbols[symbol_row + 1] {
+            let column = self.symbols[symbol].column;
+            while first_number < numbers.end
+                && self.numbers[first_number].column_end + reach < column
+            {
+                first_number += 1;
+            }
+            for number in first_number..numbers.end {
+                if self.numbers[number].column_start > column + reach {
+                    break;
+                }
+                self.symbol_numbers[symbol].push(number);
+                self.number_symbols[number].push(symbol);
+            }
+        }
+    }
+
+    // Every number of the schematic in reading order, next to a symbol or not
+    pub fn numbers(&self) -> &[PartNumber] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[CharInfo] {
+        &self.symbols
+    }
+
+    // The numbers next to the symbol with the given place in symbols()
+    pub fn numbers_next_to(&self, symbol: usize) -> impl Iterator<Item = &PartNumber> + '_ {
+        self.symbol_numbers[symbol]
+            .iter()
+            .map(|&number| &self.numbers[number])
+    }
+
+    // The symbols next to the number with the given place in numbers()
+    pub fn symbols_next_to(&self, number: usize) -> impl Iterator<Item = &CharInfo> + '_ {
+        self.number_symbols[number]
+            .iter()
+            .map(|&symbol| &self.symbols[symbol])
+    }
+
+    // The places in numbers() of the numbers next to a symbol
+    pub(crate) fn number_links(&self, symbol: usize) -> &[usize] {
+        &self.symbol_numbers[symbol]
+    }
+
+    // The places in symbols() of the symbols next to a number
+    pub(crate) fn symbol_links(&self, number: usize) -> &[usize] {
+        &self.number_symbols[number]
+    }
+
+    // The numbers next to at least one symbol, each once however many symbols it touches
+    pub fn part_numbers(&self) -> impl Iterator<Item = &PartNumber> + '_ {
+        self.numbers
+            .iter()
+            .zip(&self.number_symbols)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    pub fn rules(&self) -> &SchematicRules {
+        &self.rules
+    }
+
+    /*
+        The ratio of every gear next to as many numbers as the rules ask for, in reading order.
+        By default that's the product of the two numbers of every '*' next to exactly two.
+        A ratio too big for a usize is an error at the place of its gear
+    */
+    pub fn gear_ratios(&self) -> impl Iterator<Item = Result<usize, AocError>> + '_ {
+        self.symbols
+            .iter()
+            .zip(&self.symbol_numbers)
+            .filter(|(symbol, _)| self.rules.is_gear(symbol.character))
+            .filter_map(|(symbol, numbers)| {
+                let numbers: Links = numbers.iter().map(|&n| self.numbers[n].number).collect();
+                self.rules
+                    .gear_ratio(&numbers)
+                    .map_err(|e| e.at_line(symbol.row + 1).at_column(symbol.column + 1))
+                    .transpose()
+            })
+    }
+}
@@ -1,5 +1,31 @@
+use crate::error::{AocError, ErrorKind};
+use crate::grid::Grid;
+
+mod graph;
 mod r#impl;
+mod index;
+mod render;
+mod rules;
+
+pub use graph::{Machine, SchematicGraph};
+pub use index::SchematicIndex;
+pub use render::{CellKind, RenderMode, SchematicRenderer};
+pub use rules::{Adjacency, Combine, SchematicRules, Symbols};
 
+const DAY: usize = 3;
+
+// Adds the numbers up, an Overflow of what they are if the sum doesn't fit a usize
+fn checked_sum(
+    numbers: impl IntoIterator<Item = usize>,
+    what: &'static str,
+) -> Result<usize, AocError> {
+    numbers
+        .into_iter()
+        .try_fold(0_usize, |sum, number| sum.checked_add(number))
+        .ok_or_else(|| AocError::day(DAY, ErrorKind::Overflow { what }))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CharInfo {
     pub character: char,
     pub row: usize,
@@ -8,11 +34,7 @@ pub struct CharInfo {
 
 pub struct Symbol(CharInfo);
 
-fn is_symbol(character: &char) -> bool {
-    !character.is_digit(10) && *character != '.'
-}
-
-#[derive(Eq, PartialEq, Hash, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
 pub struct PartNumber {
     pub number: usize,
     pub row: usize,
@@ -20,8 +42,8 @@ pub struct PartNumber {
     pub column_end: usize,
 }
 
+#[derive(Debug)]
 pub struct Schematic {
-    chars: Vec<Vec<char>>,
-    width: usize,
-    height: usize,
+    chars: Grid<char>,
+    index: SchematicIndex,
 }
@@ -0,0 +1,208 @@
+use crate::day_3_gear_ratios::Schematic;
+use crate::grid::Grid;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+// What a cell of the schematic turned out to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellKind {
+    // A digit of a number next to a symbol
+    PartNumber,
+    // A digit of a number next to no symbol
+    LoneNumber,
+    // A gear symbol with the right amount of numbers next to it
+    Gear,
+    // Any other symbol, gear symbols next to the wrong amount of numbers included
+    Symbol,
+    // The blank, and chars the rules ignore
+    Blank,
+}
+
+impl CellKind {
+    // The letter under the cell in plain text
+    fn marker(&self) -> char {
+        match self {
+            CellKind::PartNumber => 'p',
+            CellKind::LoneNumber => 'x',
+            CellKind::Gear => 'g',
+            CellKind::Symbol => 's',
+            CellKind::Blank => ' ',
+        }
+    }
+
+    fn ansi(&self) -> Option<&'static str> {
+        match self {
+            CellKind::PartNumber => Some("\x1b[32m"),
+            CellKind::LoneNumber => Some("\x1b[31m"),
+            CellKind::Gear => Some("\x1b[1;33m"),
+            CellKind::Symbol => Some("\x1b[36m"),
+            CellKind::Blank => None,
+        }
+    }
+
+    fn html_class(&self) -> Option<&'static str> {
+        match self {
+            CellKind::PartNumber => Some("part"),
+            CellKind::LoneNumber => Some("lone"),
+            CellKind::Gear => Some("gear"),
+            CellKind::Symbol => Some("symbol"),
+            CellKind::Blank => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    // Colours for terminals: part numbers green, lone numbers red, gears yellow, symbols cyan
+    Ansi,
+    // Every row, followed by a row of markers: p part number, x lone number, g gear, s symbol
+    Plain,
+    // A <pre> block with the cells in <span>s of class part, lone, gear or symbol
+    Html,
+}
+
+/*
+    Writes the schematic back with what every cell turned out to be, according to its rules.
+    Without a window the whole schematic is written, with one only the cells in it
+*/
+#[derive(Debug, Clone)]
+pub struct SchematicRenderer<'a> {
+    schematic: &'a Schematic,
+    mode: RenderMode,
+    rows: RangeInclusive<usize>,
+    columns: RangeInclusive<usize>,
+}
+
+impl<'a> SchematicRenderer<'a> {
+    pub fn new(schematic: &'a Schematic) -> SchematicRenderer<'a> {
+        let chars = schematic.chars();
+        SchematicRenderer {
+            schematic,
+            mode: RenderMode::Plain,
+            // Schematics always have a row and a column
+            rows: 0..=chars.height() - 1,
+            columns: 0..=chars.width() - 1,
+        }
+    }
+
+    pub fn mode(mut self, mode: RenderMode) -> SchematicRenderer<'a> {
+        self.mode = mode;
+        self
+    }
+
+    /*
+        Only the cells at most radius rows and columns away from the given one.
+        A cell outside the schematic leaves nothing to write
+    */
+    pub fn around(mut self, row: usize, column: usize, radius: usize) -> SchematicRenderer<'a> {
+        let chars = self.schematic.chars();
+        self.rows = row.saturating_sub(radius)..=row.saturating_add(radius).min(chars.height() - 1);
+        self.columns =
+            column.saturating_sub(radius)..=column.saturating_add(radius).min(chars.width() - 1);
+        self
+    }
+
+    // Every cell of the schematic, whatever the window
+    pub fn kinds(&self) -> Grid<CellKind> {
+        let chars = self.schematic.chars();
+        let index = self.schematic.index();
+        let mut kinds = Grid::new(chars.height(), chars.width(), CellKind::Blank);
+        for (i, number) in index.numbers().iter().enumerate() {
+            let kind = match index.symbols_next_to(i).next() {
+                Some(_) => CellKind::PartNumber,
+                None => CellKind::LoneNumber,
+            };
+            for column in number.column_start..=number.column_end {
+                kinds[(number.row, column)] = kind;
+            }
+        }
+        let rules = index.rules();
+        for (i, symbol) in index.symbols().iter().enumerate() {
+            kinds[(symbol.row, symbol.column)] =
+                if rules.makes_gear(symbol.character, index.numbers_next_to(i).count()) {
+                    CellKind::Gear
+                } else {
+                    CellKind::Symbol
+                };
+        }
+        kinds
+    }
+
+    // The runs of cells of the same kind in a row of the window
+    fn runs(&self, kinds: &Grid<CellKind>, row: usize) -> Vec<(CellKind, String)> {
+        let mut runs: Vec<(CellKind, String)> = vec![];
+        for column in self.columns.clone() {
+            let kind = kinds[(row, column)];
+            let character = self.schematic.chars()[(row, column)];
+            match runs.last_mut() {
+                Some((last, run)) if *last == kind => run.push(character),
+                _ => runs.push((kind, character.to_string())),
+            }
+        }
+        runs
+    }
+
+    pub fn render(&self) -> String {
+        let kinds = self.kinds();
+        let mut rendered = String::new();
+        if self.mode == RenderMode::Html {
+            rendered.push_str("<pre class=\"schematic\">\n");
+        }
+        for row in self.rows.clone() {
+            let runs = self.runs(&kinds, row);
+            for (kind, run) in &runs {
+                match self.mode {
+                    RenderMode::Ansi => match kind.ansi() {
+                        Some(colour) => rendered.push_str(&format!("{}{}\x1b[0m", colour, run)),
+                        None => rendered.push_str(run),
+                    },
+                    RenderMode::Plain => rendered.push_str(run),
+                    RenderMode::Html => match kind.html_class() {
+                        Some(class) => rendered.push_str(&format!(
+                            "<span class=\"{}\">{}</span>",
+                            class,
+                            escape_html(run)
+                        )),
+                        None => rendered.push_str(&escape_html(run)),
+                    },
+                }
+            }
+            rendered.push('\n');
+            if self.mode == RenderMode::Plain {
+                let markers: String = self
+                    .columns
+                    .clone()
+                    .map(|column| kinds[(row, column)].marker())
+                    .collect();
+                rendered.push_str(markers.trim_end());
+                rendered.push('\n');
+            }
+        }
+        if self.mode == RenderMode::Html {
+            rendered.push_str("</pre>\n");
+        }
+        rendered
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+// The schematic as it was read
+impl Display for Schematic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chars().render(|c| *c))
+    }
+}
@@ -0,0 +1,187 @@
+use crate::day_3_gear_ratios::DAY;
+use crate::error::{AocError, ErrorKind};
+use std::fmt;
+use std::sync::Arc;
+
+// Which chars are symbols
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Symbols {
+    // Anything but digits and the blank
+    Any,
+    Only(Vec<char>),
+}
+
+// Which cells around a symbol a number has to be in to be next to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjacency {
+    // The 8 cells around it, diagonals included
+    Eight,
+    // Only the cells above, below, left and right of it
+    Four,
+}
+
+type CombineFn = dyn Fn(&[usize]) -> usize + Send + Sync;
+
+// How the numbers next to a gear make its ratio
+#[derive(Clone)]
+pub enum Combine {
+    Product,
+    Sum,
+    Custom(Arc<CombineFn>),
+}
+
+impl Combine {
+    pub fn custom(f: impl Fn(&[usize]) -> usize + Send + Sync + 'static) -> Combine {
+        Combine::Custom(Arc::new(f))
+    }
+
+    // None when the ratio doesn't fit a usize
+    pub fn apply(&self, numbers: &[usize]) -> Option<usize> {
+        match self {
+            Combine::Product => numbers
+                .iter()
+                .try_fold(1_usize, |product, &number| product.checked_mul(number)),
+            Combine::Sum => numbers
+                .iter()
+                .try_fold(0_usize, |sum, &number| sum.checked_add(number)),
+            Combine::Custom(f) => Some(f(numbers)),
+        }
+    }
+}
+
+impl fmt::Debug for Combine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Combine::Product => write!(f, "Product"),
+            Combine::Sum => write!(f, "Sum"),
+            Combine::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/*
+    How a schematic is read: which chars are symbols and which is the blank, which symbols
+    are gears and how many numbers they need next to them, and how those numbers make the ratio.
+    The default is the puzzle's: any symbol, '.', and '*' with exactly two numbers multiplied
+*/
+#[derive(Debug, Clone)]
+pub struct SchematicRules {
+    symbols: Symbols,
+    blank: char,
+    gears: Vec<char>,
+    gear_numbers: usize,
+    combine: Combine,
+    adjacency: Adjacency,
+}
+
+impl Default for SchematicRules {
+    fn default() -> SchematicRules {
+        SchematicRules {
+            symbols: Symbols::Any,
+            blank: '.',
+            gears: vec!['*'],
+            gear_numbers: 2,
+            combine: Combine::Product,
+            adjacency: Adjacency::Eight,
+        }
+    }
+}
+
+impl SchematicRules {
+    // Only these chars are symbols, any other char that isn't a digit is ignored like the blank
+    pub fn symbols(mut self, symbols: impl IntoIterator<Item = char>) -> SchematicRules {
+        self.symbols = Symbols::Only(symbols.into_iter().collect());
+        self
+    }
+
+    pub fn any_symbol(mut self) -> SchematicRules {
+        self.symbols = Symbols::Any;
+        self
+    }
+
+    pub fn blank(mut self, blank: char) -> SchematicRules {
+        self.blank = blank;
+        self
+    }
+
+    // Gears are symbols too, whether they are in the symbol set or not
+    pub fn gears(mut self, gears: impl IntoIterator<Item = char>) -> SchematicRules {
+        self.gears = gears.into_iter().collect();
+        self
+    }
+
+    // How many numbers have to be next to a gear for it to have a ratio
+    pub fn gear_numbers(mut self, gear_numbers: usize) -> SchematicRules {
+        self.gear_numbers = gear_numbers;
+        self
+    }
+
+    pub fn combine(mut self, combine: Combine) -> SchematicRules {
+        self.combine = combine;
+        self
+    }
+
+    pub fn adjacency(mut self, adjacency: Adjacency) -> SchematicRules {
+        self.adjacency = adjacency;
+        self
+    }
+
+    pub fn get_adjacency(&self) -> Adjacency {
+        self.adjacency
+    }
+
+    pub fn is_symbol(&self, character: char) -> bool {
+        if character.is_ascii_digit() || character == self.blank {
+            return false;
+        }
+        match &self.symbols {
+            Symbols::Any => true,
+            Symbols::Only(symbols) => symbols.contains(&character) || self.is_gear(character),
+        }
+    }
+
+    pub fn is_gear(&self, character: char) -> bool {
+        self.gears.contains(&character)
+    }
+
+    // Whether a symbol with this many numbers next to it is a gear with a ratio
+    pub fn makes_gear(&self, character: char, numbers: usize) -> bool {
+        self.is_gear(character) && numbers == self.gear_numbers
+    }
+
+    /*
+        The ratio of a gear with these numbers next to it, None if it doesn't have the right amount
+        of them, and an error if the ratio doesn't fit
+    */
+    pub fn gear_ratio(&self, numbers: &[usize]) -> Result<Option<usize>, AocError> {
+        if numbers.len() != self.gear_numbers {
+            return Ok(None);
+        }
+        self.combine
+            .apply(numbers)
+            .map(Some)
+            .ok_or_else(|| AocError::day(DAY, ErrorKind::Overflow { what: "gear ratio" }))
+    }
+
+    // Digits always make numbers, so none of the chars with a meaning of their own can be one
+    pub fn validate(&self) -> Result<(), AocError> {
+        let mut special = vec![self.blank];
+        special.extend(&self.gears);
+        if let Symbols::Only(symbols) = &self.symbols {
+            special.extend(symbols);
+        }
+        if let Some(digit) = special.iter().find(|c| c.is_ascii_digit()) {
+            return Err(AocError::invalid(
+                DAY,
+                format!("'{}' is a digit, it can't be a symbol or the blank", digit),
+            ));
+        }
+        if self.gears.contains(&self.blank) {
+            return Err(AocError::invalid(
+                DAY,
+                format!("'{}' is the blank, it can't be a gear", self.blank),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,22 @@
+use crate::error::{AocError, ErrorKind};
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
-use std::cmp::min;
 use std::collections::HashSet;
 use std::str::FromStr;
 
+mod rules;
+mod stream;
+mod trace;
+
+pub use rules::{
+    CappedPoints, CopyRule, DoublingPoints, EveryKthCard, FibonacciPoints, LinearPoints, NextCards,
+    PointsRule, PreviousCards, WrapAround,
+};
+pub use stream::{PileEvaluator, PileTotals};
+pub use trace::{CardTrace, Contribution, PileTrace};
+
+const DAY: usize = 4;
+
 pub struct ScratchCard {
     pub id: usize,
     pub winning_numbers: HashSet<usize>,
@@ -12,21 +26,34 @@ pub struct ScratchCard {
 pub struct ScratchPile(Vec<ScratchCard>);
 
 impl FromStr for ScratchCard {
-    type Err = String;
+    type Err = AocError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let starter_string = s.trim().replace("Card ", "");
-        let (id_token, numbers_token) = starter_string
-            .split_once(":")
-            .ok_or("Unable to parse since the colon is missing".to_string())?;
+        let (id_token, numbers_token) = starter_string.split_once(":").ok_or(AocError::day(
+            DAY,
+            ErrorKind::MissingDelimiter {
+                what: "card",
+                delimiter: ":",
+            },
+        ))?;
 
-        let id: usize = id_token
-            .trim()
-            .parse()
-            .or(Err("Unable to parse since there is no card ID"))?;
-        let (winning_numbers_token, actual_numbers_token) = numbers_token
-            .trim()
-            .split_once("|")
-            .ok_or("There was no delimiter in the numbers token, unable to parse!")?;
+        let id: usize = id_token.trim().parse().map_err(|_| {
+            AocError::day(
+                DAY,
+                ErrorKind::InvalidNumber {
+                    what: "card id",
+                    token: id_token.trim().to_string(),
+                },
+            )
+        })?;
+        let (winning_numbers_token, actual_numbers_token) =
+            numbers_token.trim().split_once("|").ok_or(AocError::day(
+                DAY,
+                ErrorKind::MissingDelimiter {
+                    what: "card numbers",
+                    delimiter: "|",
+                },
+            ))?;
 
         let winning_numbers = Self::parse_list_of_numbers(winning_numbers_token)?
             .into_iter()
@@ -55,14 +82,18 @@ impl ScratchCard {
     pub fn matches(&self) -> Vec<usize> {
         self.match_iter().into_iter().cloned().collect_vec()
     }
-    fn parse_list_of_numbers(actual_numbers_token: &str) -> Result<Vec<usize>, String> {
-        if actual_numbers_token
+    fn parse_list_of_numbers(actual_numbers_token: &str) -> Result<Vec<usize>, AocError> {
+        if let Some(character) = actual_numbers_token
             .chars()
-            .filter(|c| !(c.is_digit(10) || *c == ' '))
-            .count()
-            != 0
+            .find(|c| !(c.is_ascii_digit() || *c == ' '))
         {
-            return Err("Unable to parse list of numbers".to_string());
+            return Err(AocError::day(
+                DAY,
+                ErrorKind::InvalidChar {
+                    what: "list of numbers",
+                    character,
+                },
+            ));
         }
 
         Ok(actual_numbers_token
@@ -80,43 +111,42 @@ impl ScratchPile {
         self.0.iter().map(|card| card.match_count()).sum()
     }
 
+    /*
+        The puzzle's answers, for piles known to be small enough. They panic where points_with and
+        instances_with give an Overflow, which the solvers use instead
+    */
     pub fn matches_double(&self) -> usize {
-        self.0
-            .iter()
-            .filter(|card| card.has_matches())
-            .map(|card| 2_usize.pow((card.match_count() - 1) as u32))
-            .sum()
+        self.points_with(&DoublingPoints)
+            .expect("The points have to fit a usize")
     }
     pub fn worth(&self) -> usize {
-        let card_count = self.0.len();
+        self.instances_with(&NextCards)
+            .expect("The card instances have to fit a usize")
+    }
+}
 
-        let mut instances: Vec<usize> = vec![1; card_count];
-        let points_per_card: Vec<usize> = self
-            .0
-            .iter()
+impl FromStr for ScratchPile {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cards = s
+            .lines()
             .enumerate()
-            .map(|(i, card)| min(card_count - i - 1, card.match_count()))
-            // .map(|(i, j)| i + j)
-            .collect_vec();
+            .map(|(line_n, line)| line.parse().map_err(|e: AocError| e.at_line(line_n + 1)))
+            .collect::<Result<Vec<ScratchCard>, AocError>>()?;
+        Ok(ScratchPile(cards))
+    }
+}
 
-        points_per_card
-            .iter()
-            .enumerate()
-            .map(|(i, &points)| (0..points).for_each(|j| instances[i + j + 1] += instances[i]))
-            .collect_vec();
+impl Solution for ScratchPile {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
 
-        instances.into_iter().sum()
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.points_with(&DoublingPoints)?))
     }
-}
 
-impl FromStr for ScratchPile {
-    type Err = String;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(ScratchPile {
-            0: s.lines()
-                .map(|line| line.parse().ok())
-                .collect::<Option<Vec<ScratchCard>>>()
-                .ok_or("Unable to parse all lines of scratchpile")?,
-        })
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.instances_with(&NextCards)?))
     }
 }
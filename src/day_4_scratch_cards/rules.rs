@@ -0,0 +1,219 @@
+use crate::day_4_scratch_cards::{ScratchPile, DAY};
+use crate::error::{AocError, ErrorKind};
+use std::collections::VecDeque;
+
+// How many points a card with the given amount of matches is worth, None if it doesn't fit a usize
+pub trait PointsRule {
+    fn points(&self, match_count: usize) -> Option<usize>;
+}
+
+/*
+    Which cards a card wins a copy of, by their place in the pile, given its own place,
+    its matches and how many cards the pile has. Every copy of the card wins them again
+*/
+pub trait CopyRule {
+    fn copies(&self, card: usize, match_count: usize, pile_len: usize) -> Vec<usize>;
+}
+
+// The puzzle's: a point for the first match, doubled for every other one
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DoublingPoints;
+
+impl PointsRule for DoublingPoints {
+    fn points(&self, match_count: usize) -> Option<usize> {
+        match match_count {
+            0 => Some(0),
+            _ => 1_usize.checked_shl(u32::try_from(match_count - 1).ok()?),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LinearPoints {
+    pub per_match: usize,
+}
+
+impl PointsRule for LinearPoints {
+    fn points(&self, match_count: usize) -> Option<usize> {
+        match_count.checked_mul(self.per_match)
+    }
+}
+
+// 1, 2, 3, 5, 8... points for 1, 2, 3, 4, 5... matches
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FibonacciPoints;
+
+impl PointsRule for FibonacciPoints {
+    fn points(&self, match_count: usize) -> Option<usize> {
+        if match_count == 0 {
+            return Some(0);
+        }
+        let (mut points, mut next) = (1_usize, 2_usize);
+        for _ in 1..match_count {
+            (points, next) = (next, points.checked_add(next)?);
+        }
+        Some(points)
+    }
+}
+
+// Another rule's points, but never more than the cap, even where the other rule overflows
+#[derive(Debug, Clone, Copy)]
+pub struct CappedPoints<R: PointsRule> {
+    pub rule: R,
+    pub cap: usize,
+}
+
+impl<R: PointsRule> PointsRule for CappedPoints<R> {
+    fn points(&self, match_count: usize) -> Option<usize> {
+        Some(
+            self.rule
+                .points(match_count)
+                .map_or(self.cap, |p| p.min(self.cap)),
+        )
+    }
+}
+
+// The puzzle's: copies of the next match_count cards, none past the end of the pile
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NextCards;
+
+impl CopyRule for NextCards {
+    fn copies(&self, card: usize, match_count: usize, pile_len: usize) -> Vec<usize> {
+        (card + 1..pile_len).take(match_count).collect()
+    }
+}
+
+// Copies of the match_count cards before, none before the start of the pile
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreviousCards;
+
+impl CopyRule for PreviousCards {
+    fn copies(&self, card: usize, match_count: usize, _pile_len: usize) -> Vec<usize> {
+        (0..card).rev().take(match_count).collect()
+    }
+}
+
+// Copies of match_count cards, every k-th one after the card, none past the end of the pile
+#[derive(Debug, Clone, Copy)]
+pub struct EveryKthCard {
+    k: usize,
+}
+
+impl EveryKthCard {
+    // Every card after it is k = 1, a k of 0 is taken as 1 too
+    pub fn new(k: usize) -> EveryKthCard {
+        EveryKthCard { k: k.max(1) }
+    }
+}
+
+impl CopyRule for EveryKthCard {
+    fn copies(&self, card: usize, match_count: usize, pile_len: usize) -> Vec<usize> {
+        (card + self.k..pile_len)
+            .step_by(self.k)
+            .take(match_count)
+            .collect()
+    }
+}
+
+// Copies of the next match_count cards, going back to the top after the last one
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WrapAround;
+
+impl CopyRule for WrapAround {
+    fn copies(&self, card: usize, match_count: usize, pile_len: usize) -> Vec<usize> {
+        (1..=match_count)
+            .map(|offset| (card + offset) % pile_len)
+            .collect()
+    }
+}
+
+impl ScratchPile {
+    pub fn points_with(&self, rule: &(impl PointsRule + ?Sized)) -> Result<usize, AocError> {
+        let overflow = || AocError::day(DAY, ErrorKind::Overflow { what: "points" });
+        self.0
+            .iter()
+            .enumerate()
+            .try_fold(0_usize, |total, (i, card)| {
+                rule.points(card.match_count())
+                    .and_then(|points| total.checked_add(points))
+                    .ok_or_else(|| overflow().at_line(i + 1))
+            })
+    }
+
+    /*
+        How many cards the pile ends up being, copies included. A card's copies are all known
+        once every card winning copies of it is, so they are counted in that order. If a card
+        wins copies of itself, through other cards or not, there's no end to them and it fails,
+        on the first card in the pile that gets endless copies
+    */
+    pub fn instances_with(&self, rule: &(impl CopyRule + ?Sized)) -> Result<usize, AocError> {
+        self.cascade(rule, |_, _, _| {})?
+            .into_iter()
+            .try_fold(0_usize, |total, count| total.checked_add(count))
+            .ok_or_else(|| {
+                AocError::day(
+                    DAY,
+                    ErrorKind::Overflow {
+                        what: "card instances",
+                    },
+                )
+            })
+    }
+
+    /*
+        The instances of every card, by place in the pile. on_copy is told every time a card
+        hands out copies, with its place, the place of the card it wins and how many copies
+    */
+    pub(crate) fn cascade(
+        &self,
+        rule: &(impl CopyRule + ?Sized),
+        mut on_copy: impl FnMut(usize, usize, usize),
+    ) -> Result<Vec<usize>, AocError> {
+        let pile_len = self.0.len();
+        let copies: Vec<Vec<usize>> = self
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, card)| rule.copies(i, card.match_count(), pile_len))
+            .collect();
+        let mut winners = vec![0_usize; pile_len];
+        for &won in copies.iter().flatten() {
+            winners[won] += 1;
+        }
+
+        let mut instances = vec![1_usize; pile_len];
+        let mut ready: VecDeque<usize> = (0..pile_len).filter(|&i| winners[i] == 0).collect();
+        let mut counted = 0;
+        while let Some(card) = ready.pop_front() {
+            counted += 1;
+            for &won in &copies[card] {
+                instances[won] = instances[won].checked_add(instances[card]).ok_or_else(|| {
+                    AocError::day(
+                        DAY,
+                        ErrorKind::Overflow {
+                            what: "card instances",
+                        },
+                    )
+                    .at_line(won + 1)
+                })?;
+                on_copy(card, won, instances[card]);
+                winners[won] -= 1;
+                if winners[won] == 0 {
+                    ready.push_back(won);
+                }
+            }
+        }
+        if counted < pile_len {
+            let card = (0..pile_len).find(|&i| winners[i] > 0).unwrap();
+            return Err(AocError::invalid(
+                DAY,
+                format!(
+                    "copies of card {} never stop coming, the copy rule loops",
+                    self.0[card].id
+                ),
+            )
+            .at_line(card + 1));
+        }
+        Ok(instances)
+    }
+}
@@ -0,0 +1,101 @@
+use crate::day_4_scratch_cards::{ScratchCard, ScratchPile, DAY};
+use crate::error::{AocError, ErrorKind};
+use crate::inputs::{for_each_normalized_line, Charset};
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive, Zero};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+/*
+    The points of a pile and how many cards it ends up being, copies included.
+    Copies double with every card that wins some, so neither has to fit in a usize
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PileTotals {
+    pub cards: usize,
+    pub points: BigUint,
+    pub instances: BigUint,
+}
+
+impl PileTotals {
+    pub fn points_usize(&self) -> Result<usize, AocError> {
+        to_usize(&self.points, "points")
+    }
+
+    pub fn instances_usize(&self) -> Result<usize, AocError> {
+        to_usize(&self.instances, "card instances")
+    }
+}
+
+fn to_usize(value: &BigUint, what: &'static str) -> Result<usize, AocError> {
+    value
+        .to_usize()
+        .ok_or_else(|| AocError::day(DAY, ErrorKind::Overflow { what }))
+}
+
+/*
+    Adds up a pile one card at a time. A card only hands copies to the next match_count cards,
+    so only the copies waiting for those are kept, and memory stays proportional to the most
+    matches of a card, however long the pile. It only follows the puzzle's own rules, DoublingPoints
+    and NextCards: a CopyRule can hand copies to cards already added up, which one pass can't undo
+*/
+#[derive(Debug, Clone, Default)]
+pub struct PileEvaluator {
+    totals: PileTotals,
+    // The copies won so far of the cards coming next, the next one first
+    pending: VecDeque<BigUint>,
+}
+
+impl PileEvaluator {
+    pub fn new() -> PileEvaluator {
+        PileEvaluator::default()
+    }
+
+    pub fn push(&mut self, card: &ScratchCard) {
+        self.push_match_count(card.match_count());
+    }
+
+    pub fn push_match_count(&mut self, match_count: usize) {
+        let copies = self.pending.pop_front().unwrap_or_default() + BigUint::one();
+        if match_count > 0 {
+            self.totals.points += BigUint::one() << (match_count - 1);
+        }
+        if self.pending.len() < match_count {
+            self.pending.resize(match_count, BigUint::zero());
+        }
+        for won in self.pending.iter_mut().take(match_count) {
+            *won += &copies;
+        }
+        self.totals.instances += copies;
+        self.totals.cards += 1;
+    }
+
+    // Copies won of cards past the end of the pile are never played
+    pub fn finish(self) -> PileTotals {
+        self.totals
+    }
+}
+
+impl ScratchPile {
+    pub fn totals(&self) -> PileTotals {
+        let mut evaluator = PileEvaluator::new();
+        for card in &self.0 {
+            evaluator.push(card);
+        }
+        evaluator.finish()
+    }
+
+    /*
+        Same as totals, but reading the pile a card at a time, so it doesn't have to fit in memory.
+        The lines are normalised like every input is, and parsed the same way from_str does
+    */
+    pub fn totals_from(reader: impl BufRead) -> Result<PileTotals, AocError> {
+        let mut evaluator = PileEvaluator::new();
+        for_each_normalized_line(DAY, reader, Charset::Ascii, |line_n, line| {
+            let card: ScratchCard = line.parse().map_err(|e: AocError| e.at_line(line_n))?;
+            evaluator.push(&card);
+            Ok(())
+        })?;
+        Ok(evaluator.finish())
+    }
+}
@@ -0,0 +1,153 @@
+use crate::day_4_scratch_cards::{CopyRule, NextCards, ScratchPile, DAY};
+use crate::error::{AocError, ErrorKind};
+use std::fmt::Write;
+
+// Copies of a card won by another one, all its instances together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contribution {
+    pub card: usize,
+    pub copies: usize,
+}
+
+/*
+    Why a card ended up with the instances it has: the original, plus the copies
+    every card winning it handed out, one per instance of that card
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardTrace {
+    pub id: usize,
+    pub match_count: usize,
+    pub matches: Vec<usize>,
+    pub instances: usize,
+    // One per card handing out copies, by its place in the pile, and named by its id
+    pub contributions: Vec<Contribution>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PileTrace {
+    pub cards: Vec<CardTrace>,
+}
+
+impl PileTrace {
+    pub fn instances(&self) -> Result<usize, AocError> {
+        self.cards
+            .iter()
+            .try_fold(0_usize, |total, card| total.checked_add(card.instances))
+            .ok_or_else(|| {
+                AocError::day(
+                    DAY,
+                    ErrorKind::Overflow {
+                        what: "card instances",
+                    },
+                )
+            })
+    }
+
+    pub fn card(&self, id: usize) -> Option<&CardTrace> {
+        self.cards.iter().find(|card| card.id == id)
+    }
+
+    /*
+        One row per card. The matches are separated by spaces, and so are the contributions,
+        written as card:copies
+    */
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("card,match_count,matches,instances,copies_from\n");
+        for card in &self.cards {
+            let matches: Vec<String> = card.matches.iter().map(usize::to_string).collect();
+            let contributions: Vec<String> = card
+                .contributions
+                .iter()
+                .map(|contribution| format!("{}:{}", contribution.card, contribution.copies))
+                .collect();
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                card.id,
+                card.match_count,
+                matches.join(" "),
+                card.instances,
+                contributions.join(" ")
+            )
+            .unwrap();
+        }
+        csv
+    }
+
+    // The same trace as a JSON object, with one line per card
+    pub fn to_json(&self) -> Result<String, AocError> {
+        let cards: Vec<String> = self
+            .cards
+            .iter()
+            .map(|card| {
+                let matches: Vec<String> = card.matches.iter().map(usize::to_string).collect();
+                let contributions: Vec<String> = card
+                    .contributions
+                    .iter()
+                    .map(|contribution| {
+                        format!(
+                            "{{\"card\": {}, \"copies\": {}}}",
+                            contribution.card, contribution.copies
+                        )
+                    })
+                    .collect();
+                format!(
+                    "    {{\"card\": {}, \"match_count\": {}, \"matches\": [{}], \"instances\": {}, \"copies_from\": [{}]}}",
+                    card.id,
+                    card.match_count,
+                    matches.join(", "),
+                    card.instances,
+                    contributions.join(", ")
+                )
+            })
+            .collect();
+        let cards = if cards.is_empty() {
+            "[]".to_string()
+        } else {
+            format!("[\n{}\n  ]", cards.join(",\n"))
+        };
+        Ok(format!(
+            "{{\n  \"instances\": {},\n  \"cards\": {}\n}}\n",
+            self.instances()?,
+            cards
+        ))
+    }
+}
+
+impl ScratchPile {
+    // How every card of the puzzle's cascade got its instances, the total is what worth gives
+    pub fn trace(&self) -> Result<PileTrace, AocError> {
+        self.trace_with(&NextCards)
+    }
+
+    pub fn trace_with(&self, rule: &(impl CopyRule + ?Sized)) -> Result<PileTrace, AocError> {
+        let mut contributions: Vec<Vec<Contribution>> = vec![vec![]; self.0.len()];
+        let mut from: Vec<Vec<(usize, usize)>> = vec![vec![]; self.0.len()];
+        let instances = self.cascade(rule, |card, won, copies| from[won].push((card, copies)))?;
+        for (won, mut from) in from.into_iter().enumerate() {
+            from.sort_unstable_by_key(|(card, _)| *card);
+            // A rule can give a card more than one copy of the same card, those are added together
+            for (card, copies) in from {
+                let id = self.0[card].id;
+                match contributions[won].last_mut() {
+                    Some(last) if last.card == id => last.copies += copies,
+                    _ => contributions[won].push(Contribution { card: id, copies }),
+                }
+            }
+        }
+        let cards = self
+            .0
+            .iter()
+            .zip(instances)
+            .zip(contributions)
+            .map(|((card, instances), contributions)| CardTrace {
+                id: card.id,
+                match_count: card.match_count(),
+                matches: card.matches(),
+                instances,
+                contributions,
+            })
+            .collect();
+        Ok(PileTrace { cards })
+    }
+}
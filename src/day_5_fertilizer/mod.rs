@@ -1,4 +1,7 @@
+use crate::error::{AocError, ErrorKind};
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
+use log::{debug, trace, warn};
 use std::cmp::{max, min, Ordering};
 use std::collections::HashMap;
 
@@ -6,6 +9,8 @@ use std::isize;
 use std::ops::Range;
 use std::str::FromStr;
 
+const DAY: usize = 5;
+
 pub struct MapRangeList {
     vector: Vec<MapRange>,
 }
@@ -91,9 +96,10 @@ impl NumberSet {
         self.vector.into_iter()
     }
     fn apply_mapping(self, map_list: &MapRangeList) -> NumberSet {
-        println!(
+        trace!(
             "Trying to map: {:?}, to maps {:?}",
-            self.vector, map_list.vector
+            self.vector,
+            map_list.vector
         );
         let mut mapped_ranges: Vec<Range<isize>> = vec![];
         let mut last_exhausted_map: usize = 0;
@@ -103,50 +109,51 @@ impl NumberSet {
         loop {
             match next_range_to_address {
                 None => {
-                    println!("Finished, all addressed");
+                    trace!("Finished, all addressed");
                     return NumberSet::from_vec(mapped_ranges);
                 }
                 Some(ref range) => {
                     let current_range = range.clone();
                     if last_exhausted_map >= map_list.vector.len() {
-                        println!("Exhausted all maps, recycling {:?}", current_range);
+                        trace!("Exhausted all maps, recycling {:?}", current_range);
                         mapped_ranges.push(current_range);
                         next_range_to_address = ranges.next()
                     } else {
                         for i in last_exhausted_map..map_list.vector.len() {
                             let last_map: &MapRange = &map_list.vector[i];
                             let map_range: &Range<isize> = &last_map.source;
-                            println!(
+                            trace!(
                                 "Trying to fractionate {:?} to map {:?}",
-                                current_range, map_range
+                                current_range,
+                                map_range
                             );
 
                             if map_range.start >= current_range.end {
-                                println!("Too smol! Recycling");
+                                trace!("Too smol! Recycling");
                                 mapped_ranges.push(current_range);
                                 next_range_to_address = ranges.next();
                                 break;
                             }
 
                             if current_range.end >= map_range.end {
-                                println!("Map exhausted");
+                                trace!("Map exhausted");
                                 last_exhausted_map += 1;
                                 if current_range.start >= map_range.end {
-                                    println!("No overlap");
+                                    trace!("No overlap");
                                     // Not overlapping, check next map
                                     continue;
                                 }
                             }
                             let intersection = max(current_range.start, map_range.start)
                                 ..min(current_range.end, map_range.end);
-                            println!("Intersection found! {:?}", intersection);
+                            trace!("Intersection found! {:?}", intersection);
                             mapped_ranges.push(last_map.get_unchecked(&intersection));
 
                             // In the left side. Its unchecked, gets recycled
                             if map_range.start > current_range.start {
                                 let left_complement =
                                     current_range.start..min(current_range.end, map_range.start);
-                                println!("Unmapped {:?}", left_complement);
+                                trace!("Unmapped {:?}", left_complement);
                                 mapped_ranges.push(left_complement);
                             }
 
@@ -156,13 +163,13 @@ impl NumberSet {
                             if current_range.end > map_range.end {
                                 let right_complement = map_range.end..current_range.end;
 
-                                println!("Leftover to retry {:?}", right_complement);
+                                trace!("Leftover to retry {:?}", right_complement);
                                 next_range_to_address = Some(right_complement);
                                 break;
                             } else {
-                                println!("Moving on");
+                                trace!("Moving on");
                                 next_range_to_address = ranges.next();
-                                println!("NEXT UP {:?}, ", next_range_to_address);
+                                trace!("NEXT UP {:?}, ", next_range_to_address);
                                 break;
                             }
                         }
@@ -209,77 +216,165 @@ impl Almanac {
     fn get_numberset_for_numberset(&self, number_set: NumberSet) -> NumberSet {
         self.get(number_set)
     }
-    pub fn get_seeds_as_ranges(&self) -> NumberSet {
-        NumberSet::from_iter(
-            self.base_values
-                .clone()
-                .chunks(2)
-                .into_iter()
-                .map(|num| {
-                    println!("Chunks: {} {}", num[0], num[1]);
-                    num
-                })
-                .map(|slice| (slice[0]..slice[0] + slice[1])),
-        )
+    // The seeds come in pairs of a start and a length, from_str makes sure none is missing its length
+    pub fn get_seeds_as_ranges(&self) -> Result<NumberSet, AocError> {
+        let ranges: Vec<Range<isize>> = self
+            .base_values
+            .chunks(2)
+            .map(|num| {
+                trace!("Chunks: {:?}", num);
+                match num {
+                    [start, length] => start
+                        .checked_add(*length)
+                        .map(|end| *start..end)
+                        .ok_or_else(|| {
+                            AocError::day(DAY, ErrorKind::Overflow { what: "seed range" })
+                        }),
+                    _ => Err(AocError::invalid(DAY, "a seed range has no length")),
+                }
+            })
+            .try_collect()?;
+        // A length of 0 or less has no seeds
+        Ok(NumberSet::from_iter(
+            ranges.into_iter().filter(|range| !range.is_empty()),
+        ))
     }
-    pub fn get_seeds_as_individual_ranges(&self) -> NumberSet {
-        NumberSet::from_iter(
-            self.base_values
-                .iter()
-                .map(|&value| (value..value + 1))
-                .clone(),
-        )
+    pub fn get_seeds_as_individual_ranges(&self) -> Result<NumberSet, AocError> {
+        let ranges: Vec<Range<isize>> = self
+            .base_values
+            .iter()
+            .map(|&value| {
+                value
+                    .checked_add(1)
+                    .map(|end| value..end)
+                    .ok_or_else(|| AocError::day(DAY, ErrorKind::Overflow { what: "seed" }))
+            })
+            .try_collect()?;
+        Ok(NumberSet::from_iter(ranges.into_iter()))
     }
-    pub fn get_lowest_seed_ranges_locations(&self) -> isize {
-        let key_ranges = self.get_seeds_as_ranges();
+    pub fn get_lowest_seed_ranges_locations(&self) -> Result<isize, AocError> {
+        let key_ranges = self.get_seeds_as_ranges()?;
         let answer = self.get(key_ranges);
-        answer.vector[0].start
+        lowest(&answer)
     }
-    pub fn get_lowest_individual_seed_location(&self) -> isize {
-        let key_ranges = self.get_seeds_as_individual_ranges();
-        self.get_numberset_for_numberset(key_ranges)
-            .iter()
-            .next()
-            .unwrap()
-            .start
+    pub fn get_lowest_individual_seed_location(&self) -> Result<isize, AocError> {
+        let key_ranges = self.get_seeds_as_individual_ranges()?;
+        lowest(&self.get_numberset_for_numberset(key_ranges))
     }
 }
 
+// Seed ranges with no length at all leave no location to pick from
+fn lowest(locations: &NumberSet) -> Result<isize, AocError> {
+    locations
+        .iter()
+        .next()
+        .map(|range| range.start)
+        .ok_or_else(|| {
+            AocError::day(
+                DAY,
+                ErrorKind::Empty {
+                    what: "seed locations",
+                },
+            )
+        })
+}
+
 impl FromStr for Almanac {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // split_once always leaves a suffix of s in the right side, so its line is easy to find
+        let line_of = |rest: &str| s[..s.len() - rest.len()].matches('\n').count() + 1;
+
         // First line is the base key
-        let (base_key_name, rest_of_tokens) = s
-            .split_once("s:")
-            .ok_or("Unable to find base key".to_string())?;
+        let (base_key_name, rest_of_tokens) = s.split_once("s:").ok_or(
+            AocError::day(
+                DAY,
+                ErrorKind::MissingDelimiter {
+                    what: "almanac base key",
+                    delimiter: "s:",
+                },
+            )
+            .at_line(1),
+        )?;
 
-        let (base_values_str, mappings) = rest_of_tokens
-            .split_once("\n")
-            .ok_or("Not enough lines!".to_string())?;
+        let (base_values_str, mappings) = rest_of_tokens.split_once("\n").ok_or(
+            AocError::day(
+                DAY,
+                ErrorKind::Empty {
+                    what: "almanac map list",
+                },
+            )
+            .at_line(1),
+        )?;
 
         let base_values: Vec<isize> = base_values_str
-            .trim()
             .split_whitespace()
-            .map(|value_str| value_str.parse())
-            .try_collect()
-            .ok()
-            .ok_or("Unable to parse base values!".to_string())?;
-        println!(
+            .map(|value_str| {
+                value_str.parse().map_err(|_| {
+                    AocError::day(
+                        DAY,
+                        ErrorKind::InvalidNumber {
+                            what: "base value",
+                            token: value_str.to_string(),
+                        },
+                    )
+                    .at_line(line_of(rest_of_tokens))
+                })
+            })
+            .try_collect()?;
+        if base_values.is_empty() {
+            return Err(AocError::day(DAY, ErrorKind::Empty { what: "seed list" })
+                .at_line(line_of(rest_of_tokens)));
+        }
+        // Part two reads the seeds as pairs of a start and a length
+        if base_values.len() % 2 == 1 {
+            return Err(AocError::invalid(
+                DAY,
+                format!(
+                    "the seeds come in pairs of a start and a length, got {} numbers",
+                    base_values.len()
+                ),
+            )
+            .at_line(line_of(rest_of_tokens)));
+        }
+        debug!(
             "Base key is '{}' and the values '{:?}'",
             base_key_name, base_values,
         );
         let mut a_to_b_map: HashMap<String, String> = HashMap::new();
         let mut b_to_value_map: HashMap<String, MapRangeList> = HashMap::new();
-        let mut remaining_text = mappings.trim().to_owned() + "\n\n";
+        let mut remaining_text = mappings.trim_start();
         loop {
             let rest = remaining_text;
-            let (a_key, rest) = rest.split_once("-to-").ok_or("Error parsing a key!")?;
+            let (a_key, rest) = rest.split_once("-to-").ok_or(
+                AocError::day(
+                    DAY,
+                    ErrorKind::MissingDelimiter {
+                        what: "map header",
+                        delimiter: "-to-",
+                    },
+                )
+                .at_line(line_of(rest)),
+            )?;
 
-            let (b_key, rest) = rest.split_once("map:").ok_or("Error Parsing b key")?;
+            let (b_key, rest) = rest.split_once("map:").ok_or(
+                AocError::day(
+                    DAY,
+                    ErrorKind::MissingDelimiter {
+                        what: "map header",
+                        delimiter: "map:",
+                    },
+                )
+                .at_line(line_of(rest)),
+            )?;
 
-            let (map_lines, rest) = rest.split_once("\n\n").ok_or("Error parsing map range")?;
-            let map_range_list: MapRangeList = map_lines.parse()?;
+            // The map lines start right after the header, so the first line is the end of the header
+            let header_line = line_of(rest);
+            let (map_lines, rest) = rest.split_once("\n\n").unwrap_or((rest, ""));
+            let map_range_list: MapRangeList = map_lines
+                .parse()
+                .map_err(|e: AocError| e.offset_line(header_line - 1))?;
 
             a_to_b_map.insert(a_key.trim().to_string(), b_key.trim().to_string());
             b_to_value_map.insert(b_key.trim().to_string(), map_range_list);
@@ -287,7 +382,7 @@ impl FromStr for Almanac {
             if rest.trim().len() == 0 {
                 break;
             }
-            remaining_text = rest.to_string();
+            remaining_text = rest.trim_start();
         }
         let base_key_name = base_key_name.trim().to_string();
         Ok(Self {
@@ -305,19 +400,21 @@ impl MapRangeList {
     }
 }
 impl FromStr for MapRangeList {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let map_list: Vec<MapRange> = s
-            .trim()
             .lines()
-            .map(|x| {
-                println!("lines {}", x);
-                x
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_n, x)| {
+                trace!("lines {}", x);
+                (line_n, x)
             })
-            .map(MapRange::from_str)
-            .map(|range| range.unwrap())
-            .collect_vec();
+            .map(|(line_n, line)| {
+                MapRange::from_str(line).map_err(|e: AocError| e.at_line(line_n + 1))
+            })
+            .try_collect()?;
         Ok(Self::from_vec(map_list))
     }
 }
@@ -333,7 +430,7 @@ impl MapRange {
     // Returns the value range based on a key range assumed to be in range
     fn get_unchecked(&self, range: &Range<isize>) -> Range<isize> {
         if range.start < self.source.start || range.end > self.source.end {
-            println!(
+            warn!(
                 "BAD MAPPING key: {:?}, source: {:?}, dest: {:?}",
                 range, self.source, self.destination,
             );
@@ -343,20 +440,39 @@ impl MapRange {
 }
 
 impl FromStr for MapRange {
-    type Err = String;
+    type Err = AocError;
 
     /*
         Takes in a clean line with the three variables
         destination_start source_start length
     */
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (destination_start, source_start, length): (isize, isize, isize) = s
-            .trim()
+        let numbers: Vec<isize> = s
             .split_whitespace()
-            .map(|substring| substring.trim().parse().ok())
-            .while_some()
-            .next_tuple()
-            .ok_or("Some items couldn't be parsed".to_string())?;
+            .map(|substring| {
+                substring.parse().map_err(|_| {
+                    AocError::day(
+                        DAY,
+                        ErrorKind::InvalidNumber {
+                            what: "map range number",
+                            token: substring.to_string(),
+                        },
+                    )
+                })
+            })
+            .try_collect()?;
+        let (destination_start, source_start, length): (isize, isize, isize) = numbers
+            .iter()
+            .cloned()
+            .collect_tuple()
+            .ok_or(AocError::day(
+                DAY,
+                ErrorKind::WrongTokenCount {
+                    what: "map range",
+                    expected: 3,
+                    found: numbers.len(),
+                },
+            ))?;
         let map_range = Self {
             source: source_start..(source_start + length),
             destination: destination_start..(destination_start + length),
@@ -365,3 +481,17 @@ impl FromStr for MapRange {
         Ok(map_range)
     }
 }
+
+impl Solution for Almanac {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.get_lowest_individual_seed_location()?))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.get_lowest_seed_ranges_locations()?))
+    }
+}
@@ -1,7 +1,11 @@
+use crate::error::{AocError, ErrorKind};
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 use std::f64;
 use std::str::FromStr;
 
+const DAY: usize = 6;
+
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub struct RaceRecord {
     pub duration_ms: isize,
@@ -94,41 +98,102 @@ impl Document {
     pub fn product_of_ways_to_beat(&self) -> isize {
         self.ways_to_beat().iter().cloned().product()
     }
+
+    /*
+        Reads the document ignoring the spaces between the numbers, so
+        "7  15   30" becomes a single race of 71530 ms
+    */
+    pub fn kerned(&self) -> Result<RaceRecord, AocError> {
+        let join = |field: fn(&RaceRecord) -> isize| -> Result<isize, AocError> {
+            let kerned: String = self
+                .race_records
+                .iter()
+                .map(|record| field(record).to_string())
+                .collect();
+            kerned.parse().map_err(|_| {
+                AocError::day(
+                    DAY,
+                    ErrorKind::InvalidNumber {
+                        what: "kerned number",
+                        token: kerned,
+                    },
+                )
+            })
+        };
+        Ok(RaceRecord {
+            duration_ms: join(|record| record.duration_ms)?,
+            distance_mm: join(|record| record.distance_mm)?,
+        })
+    }
 }
 
-fn parse_number_list<T: FromStr>(tokens: &str) -> Result<Vec<T>, String> {
+fn parse_number_list<T: FromStr>(tokens: &str) -> Result<Vec<T>, AocError> {
     tokens
-        .trim()
         .split_whitespace()
-        .map(|token| token.parse::<T>().ok())
-        .collect::<Option<Vec<T>>>()
-        .ok_or("Unable to parse number list".to_string())
+        .map(|token| {
+            token.parse::<T>().map_err(|_| {
+                AocError::day(
+                    DAY,
+                    ErrorKind::InvalidNumber {
+                        what: "record number",
+                        token: token.to_string(),
+                    },
+                )
+            })
+        })
+        .collect()
 }
 impl FromStr for Document {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut line_iter = s.trim().lines();
-
-        let [time_line, distance_line] = line_iter
-            .next_chunk()
-            .map_err(|_| "Unable to find time and distance lines".to_string())?;
-
+        let mut line_iter = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        let [(time_line_n, time_line), (distance_line_n, distance_line)] =
+            line_iter.next_chunk().map_err(|_| {
+                AocError::day(
+                    DAY,
+                    ErrorKind::WrongTokenCount {
+                        what: "document",
+                        expected: 2,
+                        found: s.lines().filter(|line| !line.trim().is_empty()).count(),
+                    },
+                )
+            })?;
+
+        let missing_colon = |line_n: usize| {
+            AocError::day(
+                DAY,
+                ErrorKind::MissingDelimiter {
+                    what: "record line",
+                    delimiter: ":",
+                },
+            )
+            .at_line(line_n + 1)
+        };
         let [_, time_number_token] = time_line
             .split(":")
             .next_chunk()
-            .map_err(|_| "Unable to split time line".to_string())?;
+            .map_err(|_| missing_colon(time_line_n))?;
 
         let [_, distance_number_token] = distance_line
             .split(":")
             .next_chunk()
-            .map_err(|_| "Unable to split distance line".to_string())?;
+            .map_err(|_| missing_colon(distance_line_n))?;
 
-        let time_millis_list: Vec<isize> = parse_number_list(time_number_token)?;
-        let distance_mm_list: Vec<isize> = parse_number_list(distance_number_token)?;
+        let time_millis_list: Vec<isize> =
+            parse_number_list(time_number_token).map_err(|e| e.at_line(time_line_n + 1))?;
+        let distance_mm_list: Vec<isize> =
+            parse_number_list(distance_number_token).map_err(|e| e.at_line(distance_line_n + 1))?;
 
         if time_millis_list.len() != distance_mm_list.len() {
-            Err("The time and distance lists don't have the same length!!".to_string())
+            Err(AocError::invalid(
+                DAY,
+                "The time and distance lists don't have the same length",
+            ))
         } else {
             let time_and_distance = time_millis_list
                 .into_iter()
@@ -143,3 +208,17 @@ impl FromStr for Document {
         }
     }
 }
+
+impl Solution for Document {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.product_of_ways_to_beat()))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.kerned()?.ways_to_beat()))
+    }
+}
@@ -1,9 +1,14 @@
+use crate::error::{AocError, ErrorKind};
+use crate::solution::{Answer, Solution};
 use itertools::enumerate;
+use log::trace;
 use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
+const DAY: usize = 7;
+
 #[derive(Copy, Clone, Debug)]
 pub struct CamelCard {
     number: usize,
@@ -88,15 +93,15 @@ impl CamelHandSet {
     }
 }
 impl FromStr for CamelHandSet {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut hands: Vec<CamelHand> = s
-            .trim()
             .lines()
-            .map(|line| line.parse::<CamelHand>().ok())
-            .collect::<Option<Vec<CamelHand>>>()
-            .ok_or("Error collecting camelhand lines")?;
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(n, line)| line.parse::<CamelHand>().map_err(|e| e.at_line(n + 1)))
+            .collect::<Result<Vec<CamelHand>, AocError>>()?;
         hands.sort();
         Ok(Self { hands })
     }
@@ -120,27 +125,45 @@ impl Ord for CamelHand {
     }
 }
 impl FromStr for CamelHand {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        println!("Parsing {}", s);
+        trace!("Parsing {}", s);
         // Has to be a line
-        let (hand_token, bid_token): (&str, &str) = s
-            .trim()
-            .split_once(" ")
-            .ok_or("Error splitting tokens".to_string())?;
-
-        let bid = bid_token
-            .trim()
-            .parse()
-            .map_err(|_| "Error parsing the bid".to_string())?;
-
-        let hand_opts: [Option<CamelCard>; HAND_SIZE] = hand_token
+        let (hand_token, bid_token): (&str, &str) =
+            s.trim().split_once(" ").ok_or(AocError::day(
+                DAY,
+                ErrorKind::MissingDelimiter {
+                    what: "hand",
+                    delimiter: " ",
+                },
+            ))?;
+
+        let bid = bid_token.trim().parse().map_err(|_| {
+            AocError::day(
+                DAY,
+                ErrorKind::InvalidNumber {
+                    what: "bid",
+                    token: bid_token.trim().to_string(),
+                },
+            )
+        })?;
+
+        let hand_chars: [char; HAND_SIZE] = hand_token
             .trim()
             .chars()
             .next_chunk::<HAND_SIZE>()
-            .map_err(|_| "Error getting elements from hand".to_string())?
-            .map(|c| CamelCard::from_char(c));
+            .map_err(|_| {
+                AocError::day(
+                    DAY,
+                    ErrorKind::WrongTokenCount {
+                        what: "hand",
+                        expected: HAND_SIZE,
+                        found: hand_token.trim().chars().count(),
+                    },
+                )
+            })?;
+        let hand_opts: [Option<CamelCard>; HAND_SIZE] = hand_chars.map(|c| CamelCard::from_char(c));
 
         let mut count_map: [u8; CAMEL_CARD_COUNT] = [0; CAMEL_CARD_COUNT];
 
@@ -150,7 +173,16 @@ impl FromStr for CamelHand {
         }; HAND_SIZE];
         let mut joker_count = 0;
         for i in 0..HAND_SIZE {
-            let camel_card = hand_opts[i].ok_or("Error parsing camel card".to_string())?;
+            let camel_card = hand_opts[i].ok_or(
+                AocError::day(
+                    DAY,
+                    ErrorKind::InvalidChar {
+                        what: "hand",
+                        character: hand_chars[i],
+                    },
+                )
+                .at_column(i + 1),
+            )?;
             if camel_card.character == 'J' {
                 joker_count += 1;
             } else {
@@ -170,7 +202,7 @@ impl FromStr for CamelHand {
             (2, 2) => Ok(2), // Two pair
             (2, 1) => Ok(1), // One pair
             (1, 1) => Ok(0), // High card
-            (_, _) => Err("Unreachable state"),
+            (_, _) => Err(AocError::invalid(DAY, "Unreachable hand type")),
         }?;
 
         let mut total_hand_strength = 0;
@@ -179,7 +211,11 @@ impl FromStr for CamelHand {
             let reverse_rank = HAND_SIZE - (i + 1); // 4 3 2 1 0
             let strength_to_add = card.number * (CAMEL_CARD_COUNT.pow(reverse_rank as u32));
             total_hand_strength += strength_to_add;
-            print!("->{},", strength_to_add);
+            trace!(
+                "{} adds {} to the strength",
+                card.character,
+                strength_to_add
+            );
         }
         total_hand_strength += hand_type as usize * CAMEL_CARD_COUNT.pow(HAND_SIZE as u32);
         Ok(CamelHand {
@@ -190,3 +226,13 @@ impl FromStr for CamelHand {
         })
     }
 }
+
+impl Solution for CamelHandSet {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.total_winnings()))
+    }
+}
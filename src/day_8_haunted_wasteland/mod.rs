@@ -1,8 +1,13 @@
 use crate::day_8_haunted_wasteland::Direction::{Left, Right};
+use crate::error::{AocError, ErrorKind};
+use crate::solution::{Answer, Solution};
 use itertools::{enumerate, Itertools};
+use log::{debug, trace};
 use std::collections::HashMap;
 use std::str::FromStr;
 
+const DAY: usize = 8;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Direction {
     Left,
@@ -16,11 +21,17 @@ pub struct GhostKey {
     last_index: usize,
 }
 impl Direction {
-    fn from_char(c: char) -> Result<Direction, String> {
+    fn from_char(c: char) -> Result<Direction, AocError> {
         match c {
             'L' => Ok(Left),
             'R' => Ok(Right),
-            _ => Err(format!("Unable to parse char ({})", c).to_string()),
+            _ => Err(AocError::day(
+                DAY,
+                ErrorKind::InvalidChar {
+                    what: "directions",
+                    character: c,
+                },
+            )),
         }
     }
 }
@@ -37,14 +48,14 @@ impl GhostMap {
         self.directions.iter().cycle()
     }
 
-    pub fn walk_across_spacetime(&self, start: usize, end: usize) -> usize {
+    pub fn walk_across_spacetime(&self, start: usize, end: usize) -> Result<usize, AocError> {
         let next_keys: Vec<&GhostKey> = self.get_all_nodes_ending_with(start);
         let mut steps_needed: Vec<usize> = vec![];
 
         for key in next_keys {
-            steps_needed.push(self.walk_until_ending(key.index, end));
+            steps_needed.push(self.walk_until_ending(key.index, end)?);
         }
-        println!("Need to walk the following amount: {:?}", steps_needed);
+        debug!("Need to walk the following amount: {:?}", steps_needed);
         // They will all match, for the first time in the mcd
         least_common_multiple(steps_needed)
     }
@@ -55,14 +66,27 @@ impl GhostMap {
             .filter(|&gk| gk.last_index == index)
             .collect_vec()
     }
-    pub fn walk(&self, start: usize, end: usize) -> usize {
+
+    /*
+        Once a walk has been at every node at every point of the directions it can only go round
+        in circles, so a walk that takes longer than that never gets where it's going
+    */
+    fn never_arrives(&self, start: &str, step_count: usize) -> Option<AocError> {
+        (step_count > self.node_map.len() * self.directions.len())
+            .then(|| AocError::invalid(DAY, format!("the walk from {} never gets there", start)))
+    }
+
+    pub fn walk(&self, start: usize, end: usize) -> Result<usize, AocError> {
         let mut next_step_key = start;
         let mut step_iterator = self.iter_directions().enumerate();
         loop {
             //
             let (step_count, direction) = step_iterator.next().unwrap();
             if next_step_key == end {
-                return step_count;
+                return Ok(step_count);
+            }
+            if let Some(error) = self.never_arrives(&self.sorted_keys[start].full, step_count) {
+                return Err(error);
             }
 
             let (left, right) = &self.node_map[next_step_key];
@@ -72,13 +96,16 @@ impl GhostMap {
             };
         }
     }
-    pub fn walk_until_ending(&self, start: usize, end: usize) -> usize {
+    pub fn walk_until_ending(&self, start: usize, end: usize) -> Result<usize, AocError> {
         let mut next_step_key = &self.sorted_keys[start];
         let mut step_iterator = self.iter_directions().enumerate();
         loop {
             let (step_count, direction) = step_iterator.next().unwrap();
             if next_step_key.last_index == end {
-                return step_count;
+                return Ok(step_count);
+            }
+            if let Some(error) = self.never_arrives(&self.sorted_keys[start].full, step_count) {
+                return Err(error);
             }
 
             let (left, right) = &self.node_map[next_step_key.index];
@@ -89,38 +116,55 @@ impl GhostMap {
         }
     }
 
-    pub fn walk_across_spacetime_from_ending(&self, start: char, end: char) -> usize {
-        let start_index = self
-            .all_endings
-            .iter()
-            .position(|key| key == &start)
-            .expect("You shouldn't do this");
-        let end_index = self
-            .all_endings
-            .iter()
-            .position(|key| key == &end)
-            .expect("You shouldn't do this");
+    pub fn walk_across_spacetime_from_ending(
+        &self,
+        start: char,
+        end: char,
+    ) -> Result<usize, AocError> {
+        let position_of = |ending: char| {
+            self.all_endings
+                .iter()
+                .position(|key| key == &ending)
+                .ok_or_else(|| {
+                    AocError::day(
+                        DAY,
+                        ErrorKind::UnknownToken {
+                            what: "node ending",
+                            token: ending.to_string(),
+                        },
+                    )
+                })
+        };
+        let start_index = position_of(start)?;
+        let end_index = position_of(end)?;
         self.walk_across_spacetime(start_index, end_index)
     }
-    pub fn walk_from_key(&self, start: String, end: String) -> usize {
-        let start_index = self
-            .sorted_keys
-            .iter()
-            .position(|key| &key.full == &start)
-            .expect("You shouldn't do this");
-        let end_index = self
-            .sorted_keys
-            .iter()
-            .position(|key| &key.full == &end)
-            .expect("You shouldn't do this");
+    pub fn walk_from_key(&self, start: String, end: String) -> Result<usize, AocError> {
+        let position_of = |wanted: &String| {
+            self.sorted_keys
+                .iter()
+                .position(|key| &key.full == wanted)
+                .ok_or_else(|| {
+                    AocError::day(
+                        DAY,
+                        ErrorKind::UnknownToken {
+                            what: "node",
+                            token: wanted.clone(),
+                        },
+                    )
+                })
+        };
+        let start_index = position_of(&start)?;
+        let end_index = position_of(&end)?;
         self.walk(start_index, end_index)
     }
 }
 fn get_smallest_factor(num: usize) -> usize {
     let upper_bound = f64::sqrt(num as f64).floor() as usize;
-    println!(
+    trace!(
         "Getting smallest factor of {}, with bound: {}",
-        num, upper_bound
+        num,
+        upper_bound
     );
     for n in 2..=upper_bound {
         if num % n == 0 {
@@ -136,24 +180,29 @@ pub fn factorize(num: usize) -> Vec<usize> {
         let factor = get_smallest_factor(last_factor);
         factors.push(factor);
         if factor == last_factor {
-            println!("Factors: {:?}", factors);
+            trace!("Factors: {:?}", factors);
             return factors;
         }
         last_factor /= factor;
     }
 }
-fn least_common_multiple(nums: Vec<usize>) -> usize {
-    nums.iter().flat_map(|n| factorize(*n)).unique().product()
+fn least_common_multiple(nums: Vec<usize>) -> Result<usize, AocError> {
+    nums.iter()
+        .flat_map(|n| factorize(*n))
+        .unique()
+        .try_fold(1_usize, |product, factor| product.checked_mul(factor))
+        .ok_or_else(|| AocError::day(DAY, ErrorKind::Overflow { what: "steps" }))
 }
 
 impl FromStr for GhostMap {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut directions: Vec<Direction> = vec![];
         let mut node_hashmap: HashMap<String, (String, String)> = HashMap::new();
 
-        for line in s.trim().lines() {
+        for (line_n, line) in s.lines().enumerate() {
+            let at_line = |e: AocError| e.at_line(line_n + 1);
             let trimmed_line = line.trim();
             if trimmed_line.is_empty() {
                 continue;
@@ -162,20 +211,40 @@ impl FromStr for GhostMap {
                 // Parse directions
                 let directions_in_line = trimmed_line
                     .chars()
-                    .map(|c| Direction::from_char(c).ok())
-                    .collect::<Option<Vec<Direction>>>()
-                    .ok_or("Unable to parse all direction chars")?;
+                    .map(Direction::from_char)
+                    .collect::<Result<Vec<Direction>, AocError>>()
+                    .map_err(at_line)?;
                 directions.extend(directions_in_line);
             } else {
                 // Parse key values
                 let (key_token, tuple_token) = trimmed_line
                     .split_once("=")
-                    .ok_or("Unable to get key and tuple from line!".to_string())?;
+                    .ok_or(AocError::day(
+                        DAY,
+                        ErrorKind::MissingDelimiter {
+                            what: "node",
+                            delimiter: "=",
+                        },
+                    ))
+                    .map_err(at_line)?;
 
+                if key_token.trim().is_empty() {
+                    return Err(at_line(AocError::day(
+                        DAY,
+                        ErrorKind::Empty { what: "node" },
+                    )));
+                }
                 let trimmed_token = tuple_token.trim().replace(")", "").replace("(", "");
                 let (left, right) = trimmed_token
                     .split_once(",")
-                    .ok_or("Unable to split the tuple token!".to_string())?;
+                    .ok_or(AocError::day(
+                        DAY,
+                        ErrorKind::MissingDelimiter {
+                            what: "node tuple",
+                            delimiter: ",",
+                        },
+                    ))
+                    .map_err(at_line)?;
                 node_hashmap.insert(
                     key_token.trim().to_string(),
                     (left.trim().to_string(), right.trim().to_string()),
@@ -183,26 +252,39 @@ impl FromStr for GhostMap {
             };
         }
 
+        if directions.is_empty() {
+            return Err(AocError::day(DAY, ErrorKind::Empty { what: "directions" }));
+        }
+        // Every node has to lead to a node that is also defined
+        if let Some(unknown) = node_hashmap
+            .values()
+            .flat_map(|(left, right)| [left, right])
+            .find(|next| !node_hashmap.contains_key(*next))
+        {
+            return Err(AocError::day(
+                DAY,
+                ErrorKind::UnknownToken {
+                    what: "node",
+                    token: unknown.clone(),
+                },
+            ));
+        }
+
         let sorted_keys = node_hashmap.keys().sorted().cloned().collect_vec();
 
+        // Keys are never empty, so they all have a last char
+        let last_char = |key: &String| key.chars().last().unwrap_or_default();
         let all_endings = sorted_keys
             .iter()
-            .map(|s| s.chars().last().expect("Has to be a key"))
+            .map(last_char)
             .unique()
             .sorted()
             .collect_vec();
 
         let mut string_to_ghostkey: HashMap<String, GhostKey> = HashMap::new();
         for (index, key) in enumerate(&sorted_keys) {
-            let last = key
-                .chars()
-                .last()
-                .expect("There has to be a last char in here");
-
-            let last_index = all_endings
-                .iter()
-                .position(|c| c == &last)
-                .expect("Impossible! There has to be one char of the given");
+            let last = last_char(key);
+            let last_index = all_endings.binary_search(&last).unwrap_or_default();
 
             let gk = GhostKey {
                 index,
@@ -236,3 +318,20 @@ impl FromStr for GhostMap {
         })
     }
 }
+
+impl Solution for GhostMap {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        // Maps meant only for the ghosts don't have to go from AAA to ZZZ, which is an error here
+        Ok(Box::new(
+            self.walk_from_key("AAA".to_string(), "ZZZ".to_string())?,
+        ))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(self.walk_across_spacetime_from_ending('A', 'Z')?))
+    }
+}
@@ -1,7 +1,10 @@
+use crate::error::{AocError, ErrorKind};
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
+const DAY: usize = 9;
+
 pub struct History {
     pub values: Vec<isize>,
 }
@@ -55,20 +58,54 @@ impl History {
     }
 }
 
-pub fn parse_report(s: &str) -> Result<Vec<History>, ParseIntError> {
+pub fn parse_report(s: &str) -> Result<Vec<History>, AocError> {
     s.lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.trim().parse())
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| line.trim().parse().map_err(|e: AocError| e.at_line(n + 1)))
         .collect()
 }
 impl FromStr for History {
-    type Err = ParseIntError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let values = s
             .split_whitespace()
-            .map(|s| s.trim().parse())
-            .collect::<Result<Vec<isize>, ParseIntError>>()?;
+            .map(|token| {
+                token.parse().map_err(|_| {
+                    AocError::day(
+                        DAY,
+                        ErrorKind::InvalidNumber {
+                            what: "history value",
+                            token: token.to_string(),
+                        },
+                    )
+                })
+            })
+            .collect::<Result<Vec<isize>, AocError>>()?;
         Ok(History { values })
     }
 }
+
+pub struct Report(pub Vec<History>);
+
+impl Solution for Report {
+    fn parse(input: &str) -> Result<Self, AocError> {
+        Ok(Report(parse_report(input)?))
+    }
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(
+            self.0.iter().map(History::naive_extrapolate).sum::<isize>(),
+        ))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Ok(Box::new(
+            self.0
+                .iter()
+                .map(History::naive_extrapolate_rev)
+                .sum::<isize>(),
+        ))
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/*
+    What went wrong, independently of where it happened.
+    The `what` fields name the thing being parsed, e.g. "game id" or "map range"
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    Empty {
+        what: &'static str,
+    },
+    MissingDelimiter {
+        what: &'static str,
+        delimiter: &'static str,
+    },
+    InvalidNumber {
+        what: &'static str,
+        token: String,
+    },
+    InvalidChar {
+        what: &'static str,
+        character: char,
+    },
+    UnknownToken {
+        what: &'static str,
+        token: String,
+    },
+    WrongTokenCount {
+        what: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    RaggedRow {
+        expected: usize,
+        found: usize,
+    },
+    Invalid(String),
+    NotImplemented {
+        part: usize,
+    },
+    MissingInput {
+        variant: String,
+        path: String,
+    },
+    Io {
+        message: String,
+    },
+    Overflow {
+        what: &'static str,
+    },
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Empty { what } => write!(f, "{} is empty", what),
+            ErrorKind::MissingDelimiter { what, delimiter } => {
+                write!(f, "{} is missing '{}'", what, delimiter)
+            }
+            ErrorKind::InvalidNumber { what, token } => write!(f, "invalid {} '{}'", what, token),
+            ErrorKind::InvalidChar { what, character } => {
+                write!(f, "unexpected character {:?} in {}", character, what)
+            }
+            ErrorKind::UnknownToken { what, token } => write!(f, "unknown {} '{}'", what, token),
+            ErrorKind::WrongTokenCount {
+                what,
+                expected,
+                found,
+            } => write!(f, "{} has {} tokens, expected {}", what, found, expected),
+            ErrorKind::RaggedRow { expected, found } => {
+                write!(f, "row has {} cells, expected {}", found, expected)
+            }
+            ErrorKind::Invalid(description) => f.write_str(description),
+            ErrorKind::NotImplemented { part } => write!(f, "part {} is not implemented", part),
+            ErrorKind::MissingInput { variant, path } => {
+                write!(f, "no '{}' input, {} can't be read", variant, path)
+            }
+            ErrorKind::Io { message } => write!(f, "unable to read the input: {}", message),
+            ErrorKind::Overflow { what } => write!(f, "{} overflows", what),
+        }
+    }
+}
+
+/*
+    The error every parser and solver in the crate returns. Besides the kind,
+    it remembers the day and the (1-based) line and column of the input it failed on,
+    whenever those are known
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    pub day: Option<usize>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub kind: ErrorKind,
+}
+
+impl AocError {
+    pub fn new(kind: ErrorKind) -> AocError {
+        AocError {
+            day: None,
+            line: None,
+            column: None,
+            kind,
+        }
+    }
+
+    pub fn day(day: usize, kind: ErrorKind) -> AocError {
+        AocError::new(kind).in_day(day)
+    }
+
+    pub fn invalid(day: usize, description: impl Into<String>) -> AocError {
+        AocError::day(day, ErrorKind::Invalid(description.into()))
+    }
+
+    // The setters below never overwrite, so the innermost parser that knows the position wins
+    pub fn in_day(mut self, day: usize) -> AocError {
+        self.day.get_or_insert(day);
+        self
+    }
+
+    pub fn at_line(mut self, line: usize) -> AocError {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    pub fn at_column(mut self, column: usize) -> AocError {
+        self.column.get_or_insert(column);
+        self
+    }
+
+    /*
+        For parsers that got handed a block of lines from the middle of the input,
+        turns the line relative to the block into a line of the whole input
+    */
+    pub fn offset_line(mut self, lines_before: usize) -> AocError {
+        self.line = self.line.map(|line| line + lines_before);
+        self
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let location = [
+            self.day.map(|day| format!("day {}", day)),
+            self.line.map(|line| format!("line {}", line)),
+            self.column.map(|column| format!("column {}", column)),
+        ];
+        let location: Vec<String> = location.into_iter().flatten().collect();
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(", "))?;
+        }
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl std::error::Error for AocError {}
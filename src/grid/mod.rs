@@ -0,0 +1,318 @@
+use crate::error::{AocError, ErrorKind};
+use std::fmt;
+use std::fmt::{Display, Formatter, Write};
+use std::ops::{Index, IndexMut};
+
+// Offsets as (rows, columns), in reading order
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/*
+    A rectangular map of cells, addressed by (row, column) from the top left corner.
+    The cells are stored row after row in a single vector
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; height * width],
+            height,
+            width,
+        }
+    }
+
+    pub fn from_fn(height: usize, width: usize, mut f: impl FnMut(usize, usize) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|row_n| (0..width).map(move |col_n| (row_n, col_n)))
+            .map(|(row_n, col_n)| f(row_n, col_n))
+            .collect();
+        Grid {
+            cells,
+            height,
+            width,
+        }
+    }
+
+    /*
+        Fails if there are no rows, or if not all of them are as long as the first one.
+        The line of a ragged row is its (1-based) position in the given rows
+    */
+    pub fn from_rows(rows: Vec<Vec<T>>, what: &'static str) -> Result<Grid<T>, AocError> {
+        let width = rows
+            .first()
+            .ok_or(AocError::new(ErrorKind::Empty { what }))?
+            .len();
+        if let Some((row_n, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(AocError::new(ErrorKind::RaggedRow {
+                expected: width,
+                found: row.len(),
+            })
+            .at_line(row_n + 1));
+        }
+        let height = rows.len();
+        Ok(Grid {
+            cells: rows.into_iter().flatten().collect(),
+            height,
+            width,
+        })
+    }
+
+    /*
+        Reads one row per line, turning every char into a cell with the given parser.
+        Blank lines are skipped, errors point to the line and column of the input they come from.
+        Lines aren't trimmed, any whitespace in them has to be a valid cell
+    */
+    pub fn parse(
+        s: &str,
+        what: &'static str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, AocError> {
+        let mut rows = vec![];
+        let mut row_lines = vec![];
+        for (line_n, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(col_n, c)| {
+                    cell(c).ok_or(
+                        AocError::new(ErrorKind::InvalidChar { what, character: c })
+                            .at_line(line_n + 1)
+                            .at_column(col_n + 1),
+                    )
+                })
+                .collect::<Result<Vec<T>, AocError>>()?;
+            rows.push(row);
+            row_lines.push(line_n + 1);
+        }
+        Grid::from_rows(rows, what).map_err(|mut e| {
+            // from_rows only knows the position among the non blank lines
+            e.line = e.line.map(|row| row_lines[row - 1]);
+            e
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, row_n: usize, col_n: usize) -> bool {
+        row_n < self.height && col_n < self.width
+    }
+
+    pub fn get(&self, row_n: usize, col_n: usize) -> Option<&T> {
+        if self.contains(row_n, col_n) {
+            Some(&self.cells[row_n * self.width + col_n])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, row_n: usize, col_n: usize) -> Option<&mut T> {
+        if self.contains(row_n, col_n) {
+            Some(&mut self.cells[row_n * self.width + col_n])
+        } else {
+            None
+        }
+    }
+
+    /*
+        The position found by moving the given amount of rows and columns,
+        as long as it's still inside the grid
+    */
+    pub fn offset(
+        &self,
+        (row_n, col_n): (usize, usize),
+        (row_offset, col_offset): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let row_n = row_n.checked_add_signed(row_offset)?;
+        let col_n = col_n.checked_add_signed(col_offset)?;
+        self.contains(row_n, col_n).then_some((row_n, col_n))
+    }
+
+    // Up, left, right and down, leaving out the ones outside the grid
+    pub fn neighbours4(
+        &self,
+        row_n: usize,
+        col_n: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset((row_n, col_n), offset))
+    }
+
+    // Every surrounding position, diagonals included, in reading order
+    pub fn neighbours8(
+        &self,
+        row_n: usize,
+        col_n: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset((row_n, col_n), offset))
+    }
+
+    pub fn row(&self, row_n: usize) -> Option<&[T]> {
+        if row_n < self.height {
+            Some(&self.cells[row_n * self.width..(row_n + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|row_n| &self.cells[row_n * self.width..(row_n + 1) * self.width])
+    }
+
+    pub fn column(&self, col_n: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        if col_n < self.width {
+            Some(self.cells.iter().skip(col_n).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|col_n| self.cells.iter().skip(col_n).step_by(self.width))
+    }
+
+    // Every position in reading order
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row_n| (0..width).map(move |col_n| (row_n, col_n)))
+    }
+
+    // Every cell with its position, in reading order
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            height: self.height,
+            width: self.width,
+        }
+    }
+
+    // The rows become the columns
+    pub fn transposed(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |row_n, col_n| {
+            self[(col_n, row_n)].clone()
+        })
+    }
+
+    pub fn rotated_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |row_n, col_n| {
+            self[(self.height - 1 - col_n, row_n)].clone()
+        })
+    }
+
+    pub fn rotated_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |row_n, col_n| {
+            self[(col_n, self.width - 1 - row_n)].clone()
+        })
+    }
+
+    // Mirrors left to right
+    pub fn flipped_horizontally(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |row_n, col_n| {
+            self[(row_n, self.width - 1 - col_n)].clone()
+        })
+    }
+
+    // Mirrors top to bottom
+    pub fn flipped_vertically(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |row_n, col_n| {
+            self[(self.height - 1 - row_n, col_n)].clone()
+        })
+    }
+
+    /*
+        Writes the grid back as text, one line per row, every row ending in a newline
+    */
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut rendered = String::with_capacity(self.height * (self.width + 1));
+        for row in self.rows() {
+            rendered.extend(row.iter().map(&mut cell));
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row_n, col_n): (usize, usize)) -> &T {
+        self.get(row_n, col_n).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is outside of a {}x{} grid",
+                row_n, col_n, self.height, self.width
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row_n, col_n): (usize, usize)) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(row_n, col_n).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is outside of a {}x{} grid",
+                row_n, col_n, height, width
+            )
+        })
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
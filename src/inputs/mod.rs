@@ -0,0 +1,203 @@
+use crate::error::{AocError, ErrorKind};
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+/*
+    Every input lives in a directory per day, named after the variant it is:
+
+        data/
+            day_01/
+                input.txt        the full puzzle input
+                example.txt      the example from the puzzle text
+                spelled_out.txt  any other variant, named after what it shows
+            day_10/
+                ...
+
+    The data directory of the crate is used unless AOC_DATA_DIR points somewhere else
+*/
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+pub const PUZZLE_INPUT: &str = "input";
+pub const EXAMPLE: &str = "example";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputStore {
+    root: PathBuf,
+}
+
+impl InputStore {
+    pub fn new(root: impl Into<PathBuf>) -> InputStore {
+        InputStore { root: root.into() }
+    }
+
+    // AOC_DATA_DIR when it's set, the data directory of the crate otherwise
+    pub fn from_env() -> InputStore {
+        match std::env::var_os(DATA_DIR_VAR) {
+            Some(root) => InputStore::new(root),
+            None => InputStore::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("data")),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn day_dir(&self, day: usize) -> PathBuf {
+        self.root.join(format!("day_{:02}", day))
+    }
+
+    pub fn path(&self, day: usize, variant: &str) -> PathBuf {
+        self.day_dir(day).join(format!("{}.txt", variant))
+    }
+
+    pub fn read(&self, day: usize, variant: &str) -> Result<String, AocError> {
+        let path = self.path(day, variant);
+        fs::read_to_string(&path).map_err(|_| {
+            AocError::day(
+                day,
+                ErrorKind::MissingInput {
+                    variant: variant.to_string(),
+                    path: path.display().to_string(),
+                },
+            )
+        })
+    }
+
+    // The names of the inputs there are for the day, in alphabetical order
+    pub fn variants(&self, day: usize) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(self.day_dir(day)) else {
+            return vec![];
+        };
+        let mut variants: Vec<String> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "txt" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_str()?.to_string())
+            })
+            .collect();
+        variants.sort();
+        variants
+    }
+}
+
+// Reads a variant of the day's input from the store AOC_DATA_DIR points to
+pub fn read_input(day: usize, variant: &str) -> Result<String, AocError> {
+    InputStore::from_env().read(day, variant)
+}
+
+/*
+    What a day accepts in its input. Most puzzles are plain ASCII, and for those
+    any other character is rejected before it reaches the parser
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    Ascii,
+    Unicode,
+}
+
+/*
+    Brings the input to the one form every parser expects, whatever editor saved it:
+    no byte order mark, '\n' line endings, spaces instead of tabs, no whitespace at the end
+    of a line and no blank lines at the end. Lines keep their numbers, so errors still
+    point to the right place in the file
+*/
+pub fn normalize(input: &str, charset: Charset) -> Result<String, AocError> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut lines = vec![];
+    for (line_n, line) in input
+        .split("\r\n")
+        .flat_map(|chunk| chunk.split(['\n', '\r']))
+        .enumerate()
+    {
+        lines.push(normalize_line(line, charset).map_err(|e| e.at_line(line_n + 1))?);
+    }
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    let mut normalized = lines.join("\n");
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    Ok(normalized)
+}
+
+// A single line, without its line ending, as normalize leaves it
+fn normalize_line(line: &str, charset: Charset) -> Result<String, AocError> {
+    if charset == Charset::Ascii {
+        if let Some((col_n, character)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+            return Err(AocError::new(ErrorKind::InvalidChar {
+                what: "input",
+                character,
+            })
+            .at_column(col_n + 1));
+        }
+    }
+    Ok(line.replace('\t', " ").trim_end().to_string())
+}
+
+/*
+    Calls f with every line of the reader and its number, reusing a single buffer,
+    so only one line is in memory at a time. Stops at the first error f returns
+*/
+pub fn for_each_line(
+    day: usize,
+    mut reader: impl BufRead,
+    mut f: impl FnMut(usize, &str) -> Result<(), AocError>,
+) -> Result<(), AocError> {
+    let mut buffer = String::new();
+    for line_n in 1.. {
+        buffer.clear();
+        let read = reader.read_line(&mut buffer).map_err(|e| {
+            AocError::day(
+                day,
+                ErrorKind::Io {
+                    message: e.to_string(),
+                },
+            )
+            .at_line(line_n)
+        })?;
+        if read == 0 {
+            break;
+        }
+        let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        f(line_n, line.strip_suffix('\r').unwrap_or(line))?;
+    }
+    Ok(())
+}
+
+/*
+    Same as normalize, but a line at a time: f gets every line of the reader as normalize leaves it,
+    with the number normalize gives it. Blank lines are held back until a line with something on it
+    comes, so the ones at the end are never passed on. Lone '\r' line endings are split too, but
+    as they are only found once a '\n' is, a reader with nothing else gets read in one go
+*/
+pub fn for_each_normalized_line(
+    day: usize,
+    reader: impl BufRead,
+    charset: Charset,
+    mut f: impl FnMut(usize, &str) -> Result<(), AocError>,
+) -> Result<(), AocError> {
+    let (mut line_n, mut blank_lines) = (0, 0);
+    for_each_line(day, reader, |read_n, read| {
+        let read = match read_n {
+            1 => read.strip_prefix('\u{feff}').unwrap_or(read),
+            _ => read,
+        };
+        for line in read.split('\r') {
+            line_n += 1;
+            let line = normalize_line(line, charset).map_err(|e| e.at_line(line_n).in_day(day))?;
+            if line.is_empty() {
+                blank_lines += 1;
+                continue;
+            }
+            for blank_n in line_n - blank_lines..line_n {
+                f(blank_n, "")?;
+            }
+            blank_lines = 0;
+            f(line_n, &line)?;
+        }
+        Ok(())
+    })
+}
@@ -2,6 +2,7 @@
 
 extern crate core;
 
+pub mod answers;
 pub mod day_10_pipe_maze;
 pub mod day_11_cosmic_expansion;
 pub mod day_12_hot_springs;
@@ -18,5 +19,10 @@ pub mod day_6_wait_for_it;
 pub mod day_7_camel_cards;
 pub mod day_8_haunted_wasteland;
 pub mod day_9_mirage_maintenance;
+pub mod error;
 pub mod friend;
+pub mod grid;
+pub mod inputs;
 pub mod sandbox;
+pub mod solution;
+pub mod timing;
@@ -1 +1,371 @@
-fn main() {}
+use advent_of_code_2023::answers::{check_in, parse_manifest, summary, Outcome};
+use advent_of_code_2023::day_1_trebuchet::TrebuchetConfig;
+use advent_of_code_2023::day_2_cube_conundrum::{CubeColor, CubeCounts, GameGenerator};
+use advent_of_code_2023::inputs::{normalize, Charset, InputStore, PUZZLE_INPUT};
+use advent_of_code_2023::solution::{get_day, Day, DAYS};
+use advent_of_code_2023::timing::{time_day, timing_table};
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: aoc2023 run --day <DAY> --part <PART> [--input <FILE> | --variant <NAME>] [--data <DIR>] [--report <FORMAT>]
+       aoc2023 bench [--day <DAY>] [--runs <RUNS>] [--data <DIR>]
+       aoc2023 check [--day <DAY>] [--answers <FILE>] [--data <DIR>]
+       aoc2023 generate --day 2 [--games <GAMES>] [--seed <SEED>]
+
+run solves a single part of a day and prints the answer.
+The puzzle input is read from FILE, or from stdin when FILE is omitted or '-'.
+With --variant it's read from the data directory instead, e.g. --variant input for
+the full puzzle input, or --variant example for the example of the puzzle.
+For day 1, --report prints how every line was read instead of the answer: the first
+and last token of each line with their byte offsets, and the lines without digits.
+FORMAT is table or json.
+
+bench times the parsing and both parts of every day (or only DAY) against its
+full input, and prints a table with the median of RUNS runs.
+
+check solves every entry of the answers manifest (DIR/answers.txt by default), or only
+the ones of DAY, and reports which answers pass, fail or are missing.
+
+generate makes up a puzzle input of GAMES games (100 by default), the same one for the same SEED.
+Only day 2 can be generated, with games played from the bag of part one.
+
+The data directory is DIR, or AOC_DATA_DIR when --data is left out, or else the one of the crate.
+Each day has its inputs in DIR/day_NN/<variant>.txt.
+
+Diagnostics are off by default, set RUST_LOG to turn them on,
+e.g. RUST_LOG=advent_of_code_2023::day_5_fertilizer=trace";
+
+struct RunArgs {
+    day: usize,
+    part: usize,
+    input: Option<String>,
+    variant: Option<String>,
+    data: Option<String>,
+    report: Option<ReportFormat>,
+}
+
+#[derive(Clone, Copy)]
+enum ReportFormat {
+    Table,
+    Json,
+}
+
+impl RunArgs {
+    fn parse(args: &[String]) -> Result<RunArgs, String> {
+        let mut day = None;
+        let mut part = None;
+        let mut input = None;
+        let mut variant = None;
+        let mut data = None;
+        let mut report = None;
+        let mut arg_iter = args.iter();
+        while let Some(flag) = arg_iter.next() {
+            let value = arg_iter
+                .next()
+                .ok_or_else(|| format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--day" | "-d" => day = Some(parse_number(flag, value)?),
+                "--part" | "-p" => part = Some(parse_number(flag, value)?),
+                "--input" | "-i" => input = Some(value.clone()),
+                "--variant" | "-v" => variant = Some(value.clone()),
+                "--data" => data = Some(value.clone()),
+                "--report" => {
+                    report = Some(match value.as_str() {
+                        "table" => ReportFormat::Table,
+                        "json" => ReportFormat::Json,
+                        _ => {
+                            return Err(format!(
+                                "The value of {} must be table or json, got '{}'",
+                                flag, value
+                            ))
+                        }
+                    })
+                }
+                _ => return Err(format!("Unknown argument {}", flag)),
+            }
+        }
+        if input.is_some() && variant.is_some() {
+            return Err("Only one of --input and --variant can be given".to_string());
+        }
+        Ok(RunArgs {
+            day: day.ok_or("Missing --day")?,
+            part: part.ok_or("Missing --part")?,
+            input,
+            variant,
+            data,
+            report,
+        })
+    }
+
+    fn read_input(&self) -> Result<String, String> {
+        if let Some(variant) = &self.variant {
+            return input_store(&self.data)
+                .read(self.day, variant)
+                .map_err(|e| e.to_string());
+        }
+        match self.input.as_deref() {
+            None | Some("-") => {
+                let mut buffer = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut buffer)
+                    .map_err(|e| format!("Unable to read stdin: {}", e))?;
+                Ok(buffer)
+            }
+            Some(path) => {
+                fs::read_to_string(path).map_err(|e| format!("Unable to read {}: {}", path, e))
+            }
+        }
+    }
+}
+
+struct BenchArgs {
+    day: Option<usize>,
+    runs: usize,
+    data: Option<String>,
+}
+
+impl BenchArgs {
+    fn parse(args: &[String]) -> Result<BenchArgs, String> {
+        let mut bench_args = BenchArgs {
+            day: None,
+            runs: 1,
+            data: None,
+        };
+        let mut arg_iter = args.iter();
+        while let Some(flag) = arg_iter.next() {
+            let value = arg_iter
+                .next()
+                .ok_or_else(|| format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--day" | "-d" => bench_args.day = Some(parse_number(flag, value)?),
+                "--runs" | "-r" => bench_args.runs = parse_number(flag, value)?,
+                "--data" => bench_args.data = Some(value.clone()),
+                _ => return Err(format!("Unknown argument {}", flag)),
+            }
+        }
+        Ok(bench_args)
+    }
+}
+
+struct CheckArgs {
+    day: Option<usize>,
+    answers: Option<String>,
+    data: Option<String>,
+}
+
+impl CheckArgs {
+    fn parse(args: &[String]) -> Result<CheckArgs, String> {
+        let mut check_args = CheckArgs {
+            day: None,
+            answers: None,
+            data: None,
+        };
+        let mut arg_iter = args.iter();
+        while let Some(flag) = arg_iter.next() {
+            let value = arg_iter
+                .next()
+                .ok_or_else(|| format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--day" | "-d" => check_args.day = Some(parse_number(flag, value)?),
+                "--answers" | "-a" => check_args.answers = Some(value.clone()),
+                "--data" => check_args.data = Some(value.clone()),
+                _ => return Err(format!("Unknown argument {}", flag)),
+            }
+        }
+        Ok(check_args)
+    }
+}
+
+struct GenerateArgs {
+    day: usize,
+    games: usize,
+    seed: u64,
+}
+
+impl GenerateArgs {
+    fn parse(args: &[String]) -> Result<GenerateArgs, String> {
+        let mut day = None;
+        let mut generate_args = GenerateArgs {
+            day: 0,
+            games: 100,
+            seed: 0,
+        };
+        let mut arg_iter = args.iter();
+        while let Some(flag) = arg_iter.next() {
+            let value = arg_iter
+                .next()
+                .ok_or_else(|| format!("Missing value for {}", flag))?;
+            match flag.as_str() {
+                "--day" | "-d" => day = Some(parse_number(flag, value)?),
+                "--games" | "-g" => generate_args.games = parse_number(flag, value)?,
+                "--seed" | "-s" => generate_args.seed = parse_number(flag, value)? as u64,
+                _ => return Err(format!("Unknown argument {}", flag)),
+            }
+        }
+        generate_args.day = day.ok_or("Missing --day")?;
+        Ok(generate_args)
+    }
+}
+
+// The directory given with --data, or the one AOC_DATA_DIR points to
+fn input_store(data: &Option<String>) -> InputStore {
+    match data {
+        Some(data) => InputStore::new(data),
+        None => InputStore::from_env(),
+    }
+}
+
+fn parse_number(flag: &str, value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("The value of {} must be a number, got '{}'", flag, value))
+}
+
+/*
+    Looks up the given day in the registry, and solves the part against the input
+*/
+fn solve(day: usize, part: usize, input: &str) -> Result<String, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Day must be between 1 and 25, got {}", day));
+    }
+    let day = get_day(day).ok_or_else(|| format!("Day {} is not implemented", day))?;
+    let answer = day.solve(part, input).map_err(|e| e.to_string())?;
+    Ok(answer.to_string())
+}
+
+/*
+    Reads the calibration document the way the part does, and lays out how every line was read
+*/
+fn calibration_report(part: usize, input: &str, format: ReportFormat) -> Result<String, String> {
+    let config = match part {
+        1 => TrebuchetConfig::digits_only(),
+        2 => TrebuchetConfig::default(),
+        _ => return Err(format!("Part must be 1 or 2, got {}", part)),
+    };
+    let document = normalize(input, Charset::Unicode).map_err(|e| e.to_string())?;
+    let report = config.report(&document);
+    let report = match format {
+        ReportFormat::Table => report.to_table(),
+        ReportFormat::Json => report.to_json(),
+    };
+    // Both formats already end in a newline
+    Ok(report.trim_end().to_string())
+}
+
+/*
+    Times every requested day against its full input, and lays the timings out as a table
+*/
+fn bench(bench_args: &BenchArgs) -> Result<String, String> {
+    let days: Vec<&Day> = match bench_args.day {
+        Some(day) => vec![get_day(day).ok_or_else(|| format!("Day {} is not implemented", day))?],
+        None => DAYS.iter().collect(),
+    };
+    let store = input_store(&bench_args.data);
+    let mut timings = vec![];
+    for day in days {
+        let input = store
+            .read(day.number, PUZZLE_INPUT)
+            .map_err(|e| e.to_string())?;
+        timings.push(time_day(day, &input, bench_args.runs).map_err(|e| e.to_string())?);
+    }
+    // The table already ends in a newline
+    Ok(timing_table(&timings).trim_end().to_string())
+}
+
+/*
+    Checks the manifest entries, printing one line per entry as it goes.
+    Fails when any of the answers is wrong
+*/
+fn check(check_args: &CheckArgs) -> Result<String, String> {
+    let store = input_store(&check_args.data);
+    let manifest_path = match &check_args.answers {
+        Some(path) => PathBuf::from(path),
+        None => store.root().join("answers.txt"),
+    };
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Unable to read {}: {}", manifest_path.display(), e))?;
+    let expected_answers =
+        parse_manifest(&manifest).map_err(|e| format!("{} {}", manifest_path.display(), e))?;
+    let mut checks = vec![];
+    for expected in expected_answers
+        .iter()
+        .filter(|expected| check_args.day.is_none_or(|day| expected.day == day))
+    {
+        let checked = check_in(expected, &store);
+        println!("{}", checked);
+        checks.push(checked);
+    }
+    let summary = summary(&checks);
+    if checks
+        .iter()
+        .any(|checked| matches!(checked.outcome, Outcome::Fail { .. }))
+    {
+        Err(summary)
+    } else {
+        Ok(summary)
+    }
+}
+
+/*
+    Makes up an input for the day, with a known answer
+*/
+fn generate(generate_args: &GenerateArgs) -> Result<String, String> {
+    if generate_args.day != 2 {
+        return Err(format!("Day {} can't be generated", generate_args.day));
+    }
+    let bag = CubeCounts::from([
+        (CubeColor::Red, 12),
+        (CubeColor::Green, 13),
+        (CubeColor::Blue, 14),
+    ]);
+    let log = GameGenerator::new(&bag, generate_args.seed)
+        .map_err(|e| e.to_string())?
+        .generate(generate_args.games);
+    // The log already ends in a newline
+    Ok(log.conundrum.to_string().trim_end().to_string())
+}
+
+fn run(args: &[String]) -> Result<String, String> {
+    match args.split_first() {
+        Some((command, rest)) if command == "run" => {
+            let run_args = RunArgs::parse(rest)?;
+            let input = run_args.read_input()?;
+            match run_args.report {
+                Some(format) if run_args.day == 1 => {
+                    calibration_report(run_args.part, &input, format)
+                }
+                Some(_) => Err("--report is only available for day 1".to_string()),
+                None => solve(run_args.day, run_args.part, &input),
+            }
+        }
+        Some((command, rest)) if command == "bench" => bench(&BenchArgs::parse(rest)?),
+        Some((command, rest)) if command == "check" => check(&CheckArgs::parse(rest)?),
+        Some((command, rest)) if command == "generate" => generate(&GenerateArgs::parse(rest)?),
+        Some((command, _)) => Err(format!(
+            "Unknown command {}, run with --help for usage",
+            command
+        )),
+        None => Err("Missing command, run with --help for usage".to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    env_logger::init();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    match run(&args) {
+        Ok(answer) => {
+            println!("{}", answer);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::day_10_pipe_maze::PipeMaze;
+use crate::day_11_cosmic_expansion::SpaceImage;
+use crate::day_12_hot_springs::SpringRecords;
+use crate::day_13_point_of_incidence::MirrorNotes;
+use crate::day_14_parabolic_reflector_dish::Platform;
+use crate::day_15_lens_library::InitializationSequence;
+use crate::day_18_lavaduct_lagoon::DigPlan;
+use crate::day_1_trebuchet::CalibrationDocument;
+use crate::day_2_cube_conundrum::Conundrum;
+use crate::day_3_gear_ratios::Schematic;
+use crate::day_4_scratch_cards::ScratchPile;
+use crate::day_5_fertilizer::Almanac;
+use crate::day_6_wait_for_it::Document;
+use crate::day_7_camel_cards::CamelHandSet;
+use crate::day_8_haunted_wasteland::GhostMap;
+use crate::day_9_mirage_maintenance::Report;
+use crate::error::{AocError, ErrorKind};
+use crate::inputs::{normalize, Charset};
+use std::fmt::Display;
+
+pub type Answer = Box<dyn Display>;
+
+/*
+    The common shape of every day: the puzzle input gets parsed once,
+    and both parts are answered from the parsed state
+*/
+pub trait Solution {
+    fn parse(input: &str) -> Result<Self, AocError>
+    where
+        Self: Sized;
+
+    fn part_one(&self) -> Result<Answer, AocError> {
+        Err(AocError::new(ErrorKind::NotImplemented { part: 1 }))
+    }
+
+    fn part_two(&self) -> Result<Answer, AocError> {
+        Err(AocError::new(ErrorKind::NotImplemented { part: 2 }))
+    }
+}
+
+pub struct Day {
+    pub number: usize,
+    pub title: &'static str,
+    pub charset: Charset,
+    parser: fn(&str) -> Result<Box<dyn Solution>, AocError>,
+}
+
+fn boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>, AocError> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+impl Day {
+    // Every input is normalized first, so all days see the same text whatever editor saved it
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Solution>, AocError> {
+        normalize(input, self.charset)
+            .and_then(|input| (self.parser)(&input))
+            .map_err(|e| e.in_day(self.number))
+    }
+
+    pub fn solve(&self, part: usize, input: &str) -> Result<Answer, AocError> {
+        let solution = self.parse(input)?;
+        match part {
+            1 => solution.part_one(),
+            2 => solution.part_two(),
+            _ => Err(AocError::new(ErrorKind::Invalid(format!(
+                "part must be 1 or 2, got {}",
+                part
+            )))),
+        }
+        .map_err(|e| e.in_day(self.number))
+    }
+}
+
+pub static DAYS: [Day; 16] = [
+    Day {
+        number: 1,
+        title: "Trebuchet?!",
+        charset: Charset::Unicode,
+        parser: boxed::<CalibrationDocument>,
+    },
+    Day {
+        number: 2,
+        title: "Cube Conundrum",
+        charset: Charset::Ascii,
+        parser: boxed::<Conundrum>,
+    },
+    Day {
+        number: 3,
+        title: "Gear Ratios",
+        charset: Charset::Ascii,
+        parser: boxed::<Schematic>,
+    },
+    Day {
+        number: 4,
+        title: "Scratchcards",
+        charset: Charset::Ascii,
+        parser: boxed::<ScratchPile>,
+    },
+    Day {
+        number: 5,
+        title: "If You Give A Seed A Fertilizer",
+        charset: Charset::Ascii,
+        parser: boxed::<Almanac>,
+    },
+    Day {
+        number: 6,
+        title: "Wait For It",
+        charset: Charset::Ascii,
+        parser: boxed::<Document>,
+    },
+    Day {
+        number: 7,
+        title: "Camel Cards",
+        charset: Charset::Ascii,
+        parser: boxed::<CamelHandSet>,
+    },
+    Day {
+        number: 8,
+        title: "Haunted Wasteland",
+        charset: Charset::Ascii,
+        parser: boxed::<GhostMap>,
+    },
+    Day {
+        number: 9,
+        title: "Mirage Maintenance",
+        charset: Charset::Ascii,
+        parser: boxed::<Report>,
+    },
+    Day {
+        number: 10,
+        title: "Pipe Maze",
+        charset: Charset::Ascii,
+        parser: boxed::<PipeMaze>,
+    },
+    Day {
+        number: 11,
+        title: "Cosmic Expansion",
+        charset: Charset::Ascii,
+        parser: boxed::<SpaceImage>,
+    },
+    Day {
+        number: 12,
+        title: "Hot Springs",
+        charset: Charset::Ascii,
+        parser: boxed::<SpringRecords>,
+    },
+    Day {
+        number: 13,
+        title: "Point of Incidence",
+        charset: Charset::Ascii,
+        parser: boxed::<MirrorNotes>,
+    },
+    Day {
+        number: 14,
+        title: "Parabolic Reflector Dish",
+        charset: Charset::Ascii,
+        parser: boxed::<Platform>,
+    },
+    Day {
+        number: 15,
+        title: "Lens Library",
+        charset: Charset::Ascii,
+        parser: boxed::<InitializationSequence>,
+    },
+    Day {
+        number: 18,
+        title: "Lavaduct Lagoon",
+        charset: Charset::Ascii,
+        parser: boxed::<DigPlan>,
+    },
+];
+
+pub fn get_day(number: usize) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
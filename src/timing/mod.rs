@@ -0,0 +1,133 @@
+use crate::error::{AocError, ErrorKind};
+use crate::solution::{Answer, Day, Solution};
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/*
+    How long a day takes on a given input, with parsing and each part timed on their own.
+    A part is None when the day doesn't implement it
+*/
+#[derive(Debug, Clone)]
+pub struct DayTiming {
+    pub day: usize,
+    pub title: &'static str,
+    pub parse: Duration,
+    pub part_one: Option<Duration>,
+    pub part_two: Option<Duration>,
+}
+
+impl DayTiming {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_one.unwrap_or_default() + self.part_two.unwrap_or_default()
+    }
+}
+
+// The median of the given amount of runs, so a single hiccup doesn't skew the result
+fn median_time<T>(
+    runs: usize,
+    mut f: impl FnMut() -> Result<T, AocError>,
+) -> Result<Duration, AocError> {
+    let mut times = Vec::with_capacity(runs);
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let result = f()?;
+        times.push(start.elapsed());
+        black_box(result);
+    }
+    times.sort();
+    Ok(times[times.len() / 2])
+}
+
+fn time_part(
+    runs: usize,
+    part: impl Fn() -> Result<Answer, AocError>,
+) -> Result<Option<Duration>, AocError> {
+    match median_time(runs, part) {
+        Ok(time) => Ok(Some(time)),
+        Err(AocError {
+            kind: ErrorKind::NotImplemented { .. },
+            ..
+        }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/*
+    Parses the input and solves both parts the given amount of times, keeping the median
+    of each. Parsing happens once more beforehand, so the parts always run on the same state
+*/
+pub fn time_day(day: &Day, input: &str, runs: usize) -> Result<DayTiming, AocError> {
+    let parse = median_time(runs, || day.parse(input))?;
+    let solution: Box<dyn Solution> = day.parse(input)?;
+    let in_day = |e: AocError| e.in_day(day.number);
+    Ok(DayTiming {
+        day: day.number,
+        title: day.title,
+        parse,
+        part_one: time_part(runs, || solution.part_one()).map_err(in_day)?,
+        part_two: time_part(runs, || solution.part_two()).map_err(in_day)?,
+    })
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    match time {
+        Some(time) => format!("{:.3?}", time),
+        None => "-".to_string(),
+    }
+}
+
+/*
+    Lays the timings out as a table, one row per day, with the overall total at the bottom
+*/
+pub fn timing_table(timings: &[DayTiming]) -> String {
+    let title_width = timings
+        .iter()
+        .map(|timing| timing.title.len())
+        .chain(["Total".len()])
+        .max()
+        .unwrap_or_default();
+    let mut table = String::new();
+    let mut row = |day: &str, title: &str, times: [String; 4]| {
+        let [parse, part_one, part_two, total] = times;
+        writeln!(
+            table,
+            "{:>3}  {:<title_width$}  {:>12}  {:>12}  {:>12}  {:>12}",
+            day, title, parse, part_one, part_two, total
+        )
+        .unwrap();
+    };
+    row(
+        "Day",
+        "Title",
+        ["Parse", "Part 1", "Part 2", "Total"].map(str::to_string),
+    );
+    for timing in timings {
+        row(
+            &timing.day.to_string(),
+            timing.title,
+            [
+                Some(timing.parse),
+                timing.part_one,
+                timing.part_two,
+                Some(timing.total()),
+            ]
+            .map(format_time),
+        );
+    }
+    let sum = |time: fn(&DayTiming) -> Option<Duration>| {
+        Some(timings.iter().filter_map(time).sum::<Duration>())
+    };
+    row(
+        "",
+        "Total",
+        [
+            sum(|timing| Some(timing.parse)),
+            sum(|timing| timing.part_one),
+            sum(|timing| timing.part_two),
+            sum(|timing| Some(timing.total())),
+        ]
+        .map(format_time),
+    );
+    table
+}
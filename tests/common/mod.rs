@@ -0,0 +1,28 @@
+// Helpers shared by the integration tests, not every test file uses all of them
+#![allow(dead_code)]
+
+// Small xorshift generator, so every run of a test sees the same "random" inputs
+pub struct XorShift(u64);
+
+impl XorShift {
+    // The seed must not be 0, or every number after it is 0 too
+    pub fn new(seed: u64) -> XorShift {
+        XorShift(seed)
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // A number in 0..n, 0 when n is 0
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            0
+        } else {
+            self.next() % n
+        }
+    }
+}
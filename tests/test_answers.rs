@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod tests {
+    use advent_of_code_2023::answers::{
+        check_answer, check_in, parse_manifest, summary, ExpectedAnswer, Outcome,
+    };
+    use advent_of_code_2023::inputs::InputStore;
+    use itertools::Itertools;
+    use std::fs;
+
+    fn expected(day: usize, part: usize, answer: Option<&str>) -> ExpectedAnswer {
+        ExpectedAnswer {
+            day,
+            part,
+            variant: "example".to_string(),
+            answer: answer.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn manifest_entries_are_unique_and_their_inputs_exist() {
+        let store = InputStore::from_env();
+        let manifest = fs::read_to_string(store.root().join("answers.txt")).unwrap();
+        let expected_answers = parse_manifest(&manifest).unwrap();
+        assert!(expected_answers.len() > 50);
+        for expected in expected_answers.iter() {
+            assert!(
+                store.path(expected.day, &expected.variant).is_file(),
+                "day {} has no {} input",
+                expected.day,
+                expected.variant
+            );
+        }
+        let duplicates = expected_answers
+            .iter()
+            .duplicates_by(|expected| (expected.day, expected.part, &expected.variant))
+            .collect_vec();
+        assert!(duplicates.is_empty(), "{:?}", duplicates);
+    }
+
+    #[test]
+    fn manifest_errors_point_to_the_line() {
+        let manifest = "# day part variant answer\n\n6 1 example 288\n6 one example 3\n";
+        let error = parse_manifest(manifest).err().unwrap();
+        assert_eq!(error.to_string(), "line 4: invalid part 'one'");
+
+        let error = parse_manifest("6 1 example\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1: answer entry has 3 tokens, expected 4"
+        );
+
+        let parsed = parse_manifest("  # indented comment\n15 2 input ?").unwrap();
+        assert_eq!(
+            parsed,
+            vec![ExpectedAnswer {
+                day: 15,
+                part: 2,
+                variant: "input".to_string(),
+                answer: None
+            }]
+        );
+    }
+
+    #[test]
+    fn answers_pass_fail_or_go_missing() {
+        let races = "Time:      7  15   30\nDistance:  9  40  200\n";
+        let outcome =
+            |expected: ExpectedAnswer, input: &str| check_answer(&expected, input).outcome;
+        assert_eq!(outcome(expected(6, 1, Some("288")), races), Outcome::Pass);
+        assert_eq!(
+            outcome(expected(6, 2, Some("288")), races),
+            Outcome::Fail {
+                found: "71503".to_string()
+            }
+        );
+        assert_eq!(
+            outcome(expected(6, 1, None), races),
+            Outcome::Missing {
+                reason: "no expected answer, got 288".to_string()
+            }
+        );
+        assert_eq!(
+            outcome(expected(7, 1, Some("6440")), "32T3K 765"),
+            Outcome::Missing {
+                reason: "the part is not implemented".to_string()
+            }
+        );
+        assert_eq!(
+            outcome(expected(25, 1, Some("1")), races),
+            Outcome::Missing {
+                reason: "the day is not implemented".to_string()
+            }
+        );
+        assert_eq!(
+            outcome(expected(6, 1, Some("288")), "Time: 7\n"),
+            Outcome::Fail {
+                found: "error: day 6: document has 1 tokens, expected 2".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn ghost_only_maps_fail_part_one_instead_of_panicking() {
+        let checked = check_in(
+            &ExpectedAnswer {
+                day: 8,
+                part: 1,
+                variant: "ghost_example".to_string(),
+                answer: Some("6".to_string()),
+            },
+            &InputStore::from_env(),
+        );
+        assert_eq!(
+            checked.to_string(),
+            "FAIL    day 8 part 1 (ghost_example): \
+             expected 6, got error: day 8: unknown node 'AAA'"
+        );
+    }
+
+    #[test]
+    fn missing_inputs_are_reported_and_counted() {
+        let checked = check_in(&expected(6, 1, Some("288")), &InputStore::new("./nowhere"));
+        assert_eq!(
+            checked.to_string(),
+            "MISSING day 6 part 1 (example): \
+             no 'example' input, ./nowhere/day_06/example.txt can't be read"
+        );
+        let passed = check_answer(&expected(6, 1, Some("4")), "Time: 7\nDistance: 9");
+        assert_eq!(
+            summary(&[checked.clone(), passed, checked]),
+            "1 passed, 0 failed, 2 missing"
+        );
+    }
+}
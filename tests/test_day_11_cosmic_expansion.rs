@@ -1,13 +1,12 @@
 #[cfg(test)]
 mod test_convert {
     use advent_of_code_2023::day_11_cosmic_expansion::SpaceImage;
-    use std::fs;
+    use advent_of_code_2023::inputs::read_input;
 
     #[test]
     fn cosmic_expansion_rows_and_columns_get_increased() {
-        let buffer = fs::read_to_string("./data/cosmic_expansion_input_short.txt").unwrap();
-        let cute_buffer =
-            fs::read_to_string("./data/cosmic_expansion_input_short_expanded.txt").unwrap();
+        let buffer = read_input(11, "example").unwrap();
+        let cute_buffer = read_input(11, "example_expanded").unwrap();
         let space_image: SpaceImage = buffer.parse().unwrap();
         let expanded_image = space_image.age_by(1);
         assert_eq!(format!("{}", expanded_image), format!("{}", cute_buffer));
@@ -15,7 +14,7 @@ mod test_convert {
 
     #[test]
     fn cosmic_expansion_rows_and_columns_get_increased_distance_is_correct() {
-        let buffer = fs::read_to_string("./data/cosmic_expansion_input_short.txt").unwrap();
+        let buffer = read_input(11, "example").unwrap();
         let space_image: SpaceImage = buffer.parse().unwrap();
         assert_eq!(
             space_image.find_smallest_distances(2).iter().sum::<usize>(),
@@ -25,7 +24,7 @@ mod test_convert {
 
     #[test]
     fn cosmic_expansion_by_100_rows_and_columns_get_increased_distance_is_correct() {
-        let buffer = fs::read_to_string("./data/cosmic_expansion_input_short.txt").unwrap();
+        let buffer = read_input(11, "example").unwrap();
         let space_image: SpaceImage = buffer.parse().unwrap();
         assert_eq!(
             space_image
@@ -37,7 +36,7 @@ mod test_convert {
     }
     #[test]
     fn cosmic_expansion_rows_and_columns_for_long_input_get_increased_distance_is_correct() {
-        let buffer = fs::read_to_string("./data/cosmic_expansion_input_long.txt").unwrap();
+        let buffer = read_input(11, "input").unwrap();
         let space_image: SpaceImage = buffer.parse().unwrap();
         assert_eq!(
             space_image.find_smallest_distances(2).iter().sum::<usize>(),
@@ -47,7 +46,7 @@ mod test_convert {
 
     #[test]
     fn huge_cosmic_expansion_rows_and_columns_for_long_input_get_increased_distance_is_correct() {
-        let buffer = fs::read_to_string("./data/cosmic_expansion_input_long.txt").unwrap();
+        let buffer = read_input(11, "input").unwrap();
         let space_image: SpaceImage = buffer.parse().unwrap();
         assert_eq!(
             space_image
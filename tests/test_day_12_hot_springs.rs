@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use advent_of_code_2023::day_12_hot_springs::HotSpringsMemo;
-    use std::fs;
+    use advent_of_code_2023::inputs::read_input;
 
     #[test]
     fn small_pattern_works() {
@@ -47,7 +47,7 @@ mod tests {
 
     #[test]
     fn test_case_from_challenge_part_1_works() {
-        let the_paragraph = fs::read_to_string("./data/hot_springs_input_short.txt").unwrap();
+        let the_paragraph = read_input(12, "example").unwrap();
         let mut memo = HotSpringsMemo::new();
         let arrangements = memo
             .get_arrangements_for_paragraph(the_paragraph.as_str(), 1)
@@ -58,7 +58,7 @@ mod tests {
 
     #[test]
     fn challenge_part_1_full_input_works() {
-        let the_paragraph = fs::read_to_string("./data/hot_springs_input_long.txt").unwrap();
+        let the_paragraph = read_input(12, "input").unwrap();
         let mut memo = HotSpringsMemo::new();
         let arrangements = memo
             .get_arrangements_for_paragraph(the_paragraph.as_str(), 1)
@@ -69,7 +69,7 @@ mod tests {
 
     #[test]
     fn test_case_from_challenge_part_2_works() {
-        let the_paragraph = fs::read_to_string("./data/hot_springs_input_short.txt").unwrap();
+        let the_paragraph = read_input(12, "example").unwrap();
         let mut memo = HotSpringsMemo::new();
         let arrangements = memo
             .get_arrangements_for_paragraph(the_paragraph.as_str(), 5)
@@ -80,7 +80,7 @@ mod tests {
 
     #[test]
     fn challenge_part_2_full_input_works() {
-        let the_paragraph = fs::read_to_string("./data/hot_springs_input_long.txt").unwrap();
+        let the_paragraph = read_input(12, "input").unwrap();
         let mut memo = HotSpringsMemo::new();
         let arrangements = memo
             .get_arrangements_for_paragraph(the_paragraph.as_str(), 5)
@@ -1,12 +1,11 @@
 #[cfg(test)]
 mod tests {
     use advent_of_code_2023::day_14_parabolic_reflector_dish::Platform;
-    use std::fs;
+    use advent_of_code_2023::inputs::read_input;
 
     #[test]
     fn p() {
-        let platform_str =
-            fs::read_to_string("./data/parabolic_reflector_dish_sample.txt").unwrap();
+        let platform_str = read_input(14, "example").unwrap();
         let platform: Platform = platform_str.parse().unwrap();
         println!("Before tilting:\n{}\n", platform);
         let tilted_platform: Platform = platform.tilted();
@@ -17,8 +16,7 @@ mod tests {
 
     #[test]
     fn p2() {
-        let platform_str =
-            fs::read_to_string("./data/parabolic_reflector_dish_input_long.txt").unwrap();
+        let platform_str = read_input(14, "input").unwrap();
         let platform: Platform = platform_str.parse().unwrap();
         println!("Before tilting:\n{}\n", platform);
         let tilted_platform: Platform = platform.tilted();
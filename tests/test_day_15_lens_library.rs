@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use advent_of_code_2023::day_15_lens_library::{InitializationSequence, LensHashable};
-    use std::fs;
+    use advent_of_code_2023::inputs::read_input;
 
     #[test]
     fn test_hash_function_simple() {
@@ -19,7 +19,7 @@ mod tests {
 
     #[test]
     fn test_challenge_part_one_initialization_sequence() {
-        let sample_str = fs::read_to_string("./data/lens_library_input_long.txt").unwrap();
+        let sample_str = read_input(15, "input").unwrap();
         let sequence: InitializationSequence = sample_str.parse().unwrap();
         assert_eq!(sequence.sum_of_hashes(), 508498);
     }
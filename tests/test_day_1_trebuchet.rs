@@ -15,10 +15,21 @@ In this example, the calibration values of these four lines are 12, 38, 15, and
 Consider your entire calibration document. What is the sum of all of the calibration values?
 */
 
+mod common;
+
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::day_1_trebuchet::{get_trebuchet, get_trebuchet_multiple};
-    use std::fs;
+    use crate::common::XorShift;
+    use advent_of_code_2023::day_1_trebuchet::{
+        get_all_digits, get_trebuchet, get_trebuchet_multiple, get_trebuchet_multiple_from,
+        get_trebuchet_multiple_with, get_trebuchet_with, CalibrationReport, DigitScanner,
+        MatchedToken, TokenKind, TrebuchetConfig, Vocabulary,
+    };
+    use advent_of_code_2023::error::ErrorKind;
+    use advent_of_code_2023::inputs::read_input;
+    use std::cmp::Reverse;
+    use std::io::Cursor;
+
     #[test]
     fn single_line_example_short_first_last() {
         let example = "1abc2";
@@ -50,14 +61,14 @@ mod tests {
 
     #[test]
     fn multi_line_example_short() {
-        let example = fs::read_to_string("./data/trebuchet_input_short.txt").unwrap();
+        let example = read_input(1, "example").unwrap();
         let num = get_trebuchet_multiple(&example);
         assert_eq!(num, 142);
     }
 
     #[test]
     fn multi_line_example_long() {
-        let example = fs::read_to_string("./data/trebuchet_input_long.txt").unwrap();
+        let example = read_input(1, "input").unwrap();
         let num = get_trebuchet_multiple(&example);
         assert_eq!(num, 55686);
     }
@@ -81,4 +92,274 @@ zoneight234
         let num = get_trebuchet_multiple(example);
         assert_eq!(num, 281);
     }
+
+    #[test]
+    fn overlapping_words_are_all_found() {
+        assert_eq!(get_all_digits("twone"), vec![2, 1]);
+        assert_eq!(get_all_digits("xtwone3four"), vec![2, 1, 3, 4]);
+        assert_eq!(get_all_digits("oneightwo"), vec![1, 8, 2]);
+        assert_eq!(get_trebuchet("twone"), Some(21));
+        assert_eq!(get_trebuchet("SevenINE"), Some(79));
+        assert_eq!(get_trebuchet("abc"), None);
+    }
+
+    // Every token checked at every position, the way the scanner has to agree with
+    fn naive_matches(tokens: &[(&str, u32)], line: &str) -> Vec<(usize, usize, u32)> {
+        let mut found = vec![];
+        for start in 0..line.len() {
+            let mut here: Vec<(usize, usize, u32)> = tokens
+                .iter()
+                .filter(|(token, _)| line[start..].starts_with(token))
+                .map(|(token, value)| (start, token.len(), *value))
+                .collect();
+            here.sort_by_key(|found| Reverse(found.1));
+            here.dedup_by_key(|found| found.1);
+            found.extend(here);
+        }
+        found
+    }
+
+    #[test]
+    fn scanner_agrees_with_checking_every_position() {
+        let tokens = [
+            ("ab", 1),
+            ("b", 2),
+            ("abc", 3),
+            ("bca", 4),
+            ("c", 5),
+            ("aab", 6),
+        ];
+        let scanner = DigitScanner::new(tokens);
+        let mut rng = XorShift::new(0x2545_F491_4F6C_DD1D);
+        for _ in 0..2000 {
+            let seed = rng.next();
+            let line: String = (0..(seed % 12))
+                .map(|i| ['a', 'b', 'c', 'x'][((seed >> (2 * i + 8)) % 4) as usize])
+                .collect();
+            let expected = naive_matches(&tokens, &line);
+            let found = scanner
+                .matches(&line)
+                .iter()
+                .map(|found| (found.start, found.len, found.value))
+                .collect::<Vec<_>>();
+            assert_eq!(found, expected, "{:?}", line);
+            let first = scanner
+                .first(&line)
+                .map(|found| (found.start, found.len, found.value));
+            let last = scanner
+                .last(&line)
+                .map(|found| (found.start, found.len, found.value));
+            let last_start = expected.iter().map(|found| found.0).max();
+            assert_eq!(first, expected.first().copied(), "{:?}", line);
+            assert_eq!(
+                last,
+                expected
+                    .iter()
+                    .find(|found| Some(found.0) == last_start)
+                    .copied(),
+                "{:?}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn spelled_out_words_can_be_turned_off() {
+        let digits_only = TrebuchetConfig::digits_only();
+        assert_eq!(get_trebuchet_with("two1nine", &digits_only), Some(11));
+        assert_eq!(get_trebuchet_with("eightwothree", &digits_only), None);
+        let spelled_out = digits_only.spelled_out(true);
+        assert!(spelled_out.is_spelled_out());
+        assert_eq!(get_trebuchet_with("two1nine", &spelled_out), Some(29));
+
+        let example = read_input(1, "spelled_out").unwrap();
+        let digits_only = TrebuchetConfig::default().spelled_out(false);
+        assert_eq!(get_trebuchet_multiple_with(&example, &digits_only), 209);
+        assert_eq!(
+            get_trebuchet_multiple_with(&example, &TrebuchetConfig::default()),
+            281
+        );
+    }
+
+    #[test]
+    fn other_languages_can_be_used_and_merged() {
+        let spanish = TrebuchetConfig::new(Vocabulary::spanish());
+        assert_eq!(get_trebuchet_with("cincoXsiete", &spanish), Some(57));
+        assert_eq!(get_trebuchet_with("five3seven", &spanish), Some(33));
+
+        let german = TrebuchetConfig::new(Vocabulary::for_language("DE").unwrap());
+        assert_eq!(get_trebuchet_with("FÜNFundZWANZIG eins", &german), Some(51));
+        assert!(Vocabulary::for_language("xx").is_none());
+
+        let both = TrebuchetConfig::new(Vocabulary::english().with(&Vocabulary::french()));
+        assert_eq!(both.vocabulary().len(), 20);
+        assert_eq!(both.digits("deuxfiveZÉRO"), vec![2, 5, 0]);
+    }
+
+    #[test]
+    fn custom_words_are_read_one_per_line() {
+        let vocabulary: Vocabulary = "# japanese\nichi 1\n\nni 2\nsan 3\n".parse().unwrap();
+        let config = TrebuchetConfig::new(vocabulary);
+        assert_eq!(get_trebuchet_with("sanXichi", &config), Some(31));
+
+        let error = "ichi 1\nni\n".parse::<Vocabulary>().err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 2: vocabulary entry has 1 tokens, expected 2"
+        );
+        let error = "ichi 1\njuu 10\n".parse::<Vocabulary>().err().unwrap();
+        assert_eq!(error.to_string(), "day 1, line 2: invalid digit '10'");
+
+        let mut vocabulary = Vocabulary::empty();
+        let error = vocabulary.add("  ", 1).err().unwrap();
+        assert_eq!(error.kind, ErrorKind::Empty { what: "digit word" });
+        assert!(vocabulary.is_empty());
+    }
+
+    #[test]
+    fn words_match_whatever_their_case() {
+        let greek: Vocabulary = "ένα 1\nδύο 2\nτρεις 3".parse().unwrap();
+        let config = TrebuchetConfig::new(greek);
+        assert_eq!(get_trebuchet_with("ΤΡΕΙΣ και ΔΎΟ", &config), Some(32));
+        assert_eq!(config.digits("Ένατρεις"), vec![1, 3]);
+
+        let mut german = Vocabulary::empty();
+        german.add("Straße", 5).unwrap();
+        let config = TrebuchetConfig::new(german);
+        assert_eq!(get_trebuchet_with("STRASSE", &config), Some(55));
+        assert_eq!(get_trebuchet_with("7strasse", &config), Some(75));
+    }
+
+    #[test]
+    fn report_keeps_every_line_and_where_its_tokens_are() {
+        let report = TrebuchetConfig::default().report("two1nine\nabc\nx7\n");
+        assert_eq!(report.sum(), 29 + 77);
+        assert_eq!(report.lines_without_digits, vec![2]);
+        assert_eq!(report.lines.len(), 2);
+        let first = &report.lines[0];
+        assert_eq!(first.line, 1);
+        assert_eq!(first.value(), 29);
+        assert_eq!(
+            first.first,
+            MatchedToken {
+                text: "two".to_string(),
+                start: 0,
+                end: 3,
+                value: 2,
+                kind: TokenKind::Word,
+            }
+        );
+        assert_eq!((first.last.start, first.last.end), (4, 8));
+        assert_eq!(report.lines[1].first.kind, TokenKind::Digit);
+        assert_eq!(report.lines[1].first, report.lines[1].last);
+
+        let example = read_input(1, "spelled_out").unwrap();
+        let config = TrebuchetConfig::default();
+        assert_eq!(
+            config.report(&example).sum(),
+            config.calibration_sum(&example)
+        );
+    }
+
+    #[test]
+    fn report_offsets_point_into_the_line_as_written() {
+        let config = TrebuchetConfig::new(Vocabulary::german());
+        let line = "xFÜNFundSTRAẞE7";
+        let report = config.line_report(1, line).unwrap();
+        assert_eq!(report.first.text, "FÜNF");
+        assert_eq!(&line[report.first.start..report.first.end], "FÜNF");
+        assert_eq!(report.last.text, "7");
+        assert_eq!(report.last.start, line.len() - 1);
+
+        let mut vocabulary = Vocabulary::empty();
+        vocabulary.add("strasse", 5).unwrap();
+        let config = TrebuchetConfig::new(vocabulary);
+        let report = config.line_report(3, "zStraße").unwrap();
+        assert_eq!(report.first.text, "Straße");
+        assert_eq!((report.first.start, report.first.end), (1, 8));
+    }
+
+    #[test]
+    fn report_renders_as_a_table_or_as_json() {
+        let report = TrebuchetConfig::default().report("two1nine\n\"q\"\n");
+        let table = report.to_table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                " Line  First  Kind   Bytes     Last  Kind   Bytes     Value",
+                "    1  two    word   0..3      nine  word   4..8         29",
+                "Sum: 29",
+                "Lines without digits: 2",
+            ]
+        );
+        assert_eq!(
+            report.to_json(),
+            r#"{
+  "sum": 29,
+  "lines": [
+    {"line": 1, "value": 29, "first": {"text": "two", "start": 0, "end": 3, "value": 2, "kind": "word"}, "last": {"text": "nine", "start": 4, "end": 8, "value": 9, "kind": "word"}}
+  ],
+  "lines_without_digits": [2]
+}
+"#
+        );
+        assert_eq!(
+            CalibrationReport::default().to_json(),
+            "{\n  \"sum\": 0,\n  \"lines\": [],\n  \"lines_without_digits\": []\n}\n"
+        );
+    }
+
+    #[test]
+    fn documents_can_be_summed_a_line_at_a_time() {
+        let input = read_input(1, "input").unwrap();
+        assert_eq!(get_trebuchet_multiple_from(input.as_bytes()), Ok(55686));
+        let digits_only = TrebuchetConfig::digits_only();
+        assert_eq!(
+            digits_only.calibration_sum_from(Cursor::new(&input)),
+            Ok(55029)
+        );
+        assert_eq!(
+            get_trebuchet_multiple_from("two1nine\r\nabc\r\n7".as_bytes()),
+            Ok(29 + 77)
+        );
+    }
+
+    #[test]
+    fn parallel_sums_agree_with_the_sequential_one() {
+        let input = read_input(1, "input").unwrap().repeat(10);
+        let config = TrebuchetConfig::default();
+        for threads in [0, 1, 2, 3, 16] {
+            assert_eq!(
+                config.calibration_sum_parallel(input.as_bytes(), threads),
+                Ok(556860),
+                "{} threads",
+                threads
+            );
+        }
+        assert_eq!(config.calibration_sum_parallel("".as_bytes(), 4), Ok(0));
+    }
+
+    #[test]
+    fn sums_fail_on_overflow_and_unreadable_lines() {
+        let config = TrebuchetConfig::default();
+        assert_eq!(
+            config.add_calibration_sum(u64::MAX - 100, "one\ntwo\n".as_bytes()),
+            Ok(u64::MAX - 100 + 11 + 22)
+        );
+        let error = config
+            .add_calibration_sum(u64::MAX - 20, "12\nabc\n34\n".as_bytes())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 3: calibration sum overflows"
+        );
+
+        let not_utf8: &[u8] = b"12\n\xff\xfe3\n";
+        let error = config.calibration_sum_from(not_utf8).unwrap_err();
+        assert_eq!((error.day, error.line), (Some(1), Some(2)));
+        assert!(matches!(error.kind, ErrorKind::Io { .. }));
+        let error = config.calibration_sum_parallel(not_utf8, 4).unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::Io { .. }));
+    }
 }
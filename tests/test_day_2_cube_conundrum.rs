@@ -1,7 +1,13 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::day_2_cube_conundrum::{Conundrum, CubeColor, CubeCounts, Game};
-    use std::fs;
+    use crate::common::XorShift;
+    use advent_of_code_2023::day_2_cube_conundrum::{
+        BagEstimator, Conundrum, CubeColor, CubeCounts, Game, GameGenerator, Palette,
+    };
+    use advent_of_code_2023::error::ErrorKind;
+    use advent_of_code_2023::inputs::read_input;
     use std::str::FromStr;
     #[test]
     fn parse_turn() {
@@ -211,10 +217,7 @@ mod tests {
 
     #[test]
     fn possible_games_from_challenge_example_are_correct() {
-        let conundrum: Conundrum = fs::read_to_string("./data/cube_conundrum_input_short.txt")
-            .unwrap()
-            .parse()
-            .unwrap();
+        let conundrum: Conundrum = read_input(2, "example").unwrap().parse().unwrap();
         let bag: CubeCounts = CubeCounts::from([
             (CubeColor::Red, 12),
             (CubeColor::Green, 13),
@@ -226,10 +229,7 @@ mod tests {
 
     #[test]
     fn possible_games_from_challenge_part1_are_correct() {
-        let conundrum: Conundrum = fs::read_to_string("./data/cube_conundrum_input_mid.txt")
-            .unwrap()
-            .parse()
-            .unwrap();
+        let conundrum: Conundrum = read_input(2, "input").unwrap().parse().unwrap();
         let bag: CubeCounts = CubeCounts::from([
             (CubeColor::Red, 12),
             (CubeColor::Green, 13),
@@ -240,10 +240,7 @@ mod tests {
 
     #[test]
     fn powers_of_each_games_minimum_bag_added_up_are_correct_for_the_short_example() {
-        let conundrum: Conundrum = fs::read_to_string("./data/cube_conundrum_input_short.txt")
-            .unwrap()
-            .parse()
-            .unwrap();
+        let conundrum: Conundrum = read_input(2, "example").unwrap().parse().unwrap();
 
         let powers: Vec<usize> = conundrum.powers();
         assert_eq!(powers, vec![48, 12, 1560, 630, 36]);
@@ -253,12 +250,371 @@ mod tests {
 
     #[test]
     fn powers_of_each_games_minimum_bag_added_up_are_correct_for_the_mid_example() {
-        let conundrum: Conundrum = fs::read_to_string("./data/cube_conundrum_input_mid.txt")
-            .unwrap()
-            .parse()
-            .unwrap();
+        let conundrum: Conundrum = read_input(2, "input").unwrap().parse().unwrap();
 
         let sum: usize = conundrum.powers().iter().sum();
         assert_eq!(sum, 67363);
     }
+
+    const RAINBOW_GAMES: &str = "Game 1: 3 blue, 2 yellow; 1 Black, 4 red
+Game 2: 1 yellow, 1 green, 1 black, 1 red, 1 blue
+Game 3: 7 yellow; 2 red, 1 blue, 1 green";
+
+    fn yellow() -> CubeColor {
+        CubeColor::Named("yellow".to_string())
+    }
+
+    fn black() -> CubeColor {
+        CubeColor::Named("black".to_string())
+    }
+
+    #[test]
+    fn colors_besides_the_standard_ones_go_by_name() {
+        assert_eq!("Yellow".parse::<CubeColor>().unwrap(), yellow());
+        assert_eq!(" RED ".parse::<CubeColor>().unwrap(), CubeColor::Red);
+        assert_eq!(yellow().name(), "yellow");
+        assert_eq!(CubeColor::Green.name(), "green");
+        assert_eq!(
+            "".parse::<CubeColor>().unwrap_err().kind,
+            ErrorKind::Empty { what: "cube color" }
+        );
+        let palette: Palette = "red, green blue,yellow, red".parse().unwrap();
+        assert_eq!(
+            palette.colors(),
+            &[CubeColor::Red, CubeColor::Green, CubeColor::Blue, yellow()]
+        );
+    }
+
+    #[test]
+    fn declared_palettes_reject_any_other_color() {
+        let palette: Palette = "red green blue yellow".parse().unwrap();
+        let error = Conundrum::parse_in(RAINBOW_GAMES, &palette).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ErrorKind::UnknownToken {
+                what: "cube color",
+                token: "Black".to_string()
+            }
+        );
+        assert_eq!((error.day, error.line), (Some(2), Some(1)));
+        assert!(RAINBOW_GAMES.parse::<Conundrum>().is_err());
+
+        let palette = Palette::new([yellow(), black()]);
+        let conundrum = Conundrum::parse_in("Game 1: 2 yellow; 1 black", &palette).unwrap();
+        assert_eq!(conundrum.powers_in(&palette), vec![2]);
+    }
+
+    #[test]
+    fn discovered_palettes_work_with_any_number_of_colors() {
+        let conundrum = Conundrum::parse_any(RAINBOW_GAMES).unwrap();
+        assert_eq!(
+            conundrum.palette().colors(),
+            &[
+                CubeColor::Red,
+                CubeColor::Blue,
+                CubeColor::Green,
+                black(),
+                yellow()
+            ]
+        );
+        let bag = CubeCounts::from([
+            (CubeColor::Red, 4),
+            (CubeColor::Blue, 3),
+            (CubeColor::Green, 1),
+            (yellow(), 2),
+            (black(), 1),
+        ]);
+        assert_eq!(conundrum.possible_games(&bag), vec![1, 2]);
+        assert_eq!(
+            conundrum.0[2].smallest_bag(),
+            CubeCounts::from([
+                (yellow(), 7),
+                (CubeColor::Red, 2),
+                (CubeColor::Blue, 1),
+                (CubeColor::Green, 1),
+            ])
+        );
+        // Game 1 never draws a green cube, and game 3 never draws a black one
+        assert_eq!(conundrum.powers(), vec![0, 1, 0]);
+        assert_eq!(conundrum.powers_in(&Palette::standard()), vec![0, 1, 2]);
+        assert_eq!(conundrum.0[2].smallest_bag().power(), 14);
+    }
+
+    #[test]
+    fn games_are_written_back_the_way_the_input_writes_them() {
+        let game: Game = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            game.to_string(),
+            "Game 1: 4 red, 3 blue; 1 red, 6 blue, 2 green; 2 green"
+        );
+        assert_eq!(CubeColor::Red.to_string(), "red");
+        assert_eq!(
+            CubeCounts::from([(yellow(), 2), (CubeColor::Green, 0)]).to_string(),
+            "0 green, 2 yellow"
+        );
+
+        let conundrum = Conundrum::parse_any(RAINBOW_GAMES).unwrap();
+        assert_eq!(
+            conundrum.to_string(),
+            "Game 1: 3 blue, 2 yellow; 4 red, 1 black
+Game 2: 1 red, 1 blue, 1 green, 1 black, 1 yellow
+Game 3: 7 yellow; 2 red, 1 blue, 1 green
+"
+        );
+    }
+
+    #[test]
+    fn printing_the_puzzle_input_parses_back_to_the_same_games() {
+        for variant in ["example", "input"] {
+            let conundrum: Conundrum = read_input(2, variant).unwrap().parse().unwrap();
+            let printed = conundrum.to_string();
+            assert_eq!(printed.parse::<Conundrum>().unwrap(), conundrum);
+            assert_eq!(printed.parse::<Conundrum>().unwrap().to_string(), printed);
+        }
+    }
+
+    #[test]
+    fn parse_print_parse_is_the_identity() {
+        let colors = [
+            CubeColor::Red,
+            CubeColor::Green,
+            CubeColor::Blue,
+            yellow(),
+            black(),
+        ];
+        let mut rng = XorShift::new(0x9E37_79B9_7F4A_7C15);
+        let mut next = |below: u64| rng.below(below);
+        for _ in 0..300 {
+            let games: Vec<Game> = (0..next(5))
+                .map(|_| Game {
+                    id: next(1000) as usize,
+                    turns: (0..1 + next(4))
+                        .map(|_| {
+                            // Every turn draws at least one colour, or it couldn't be written
+                            let first = next(colors.len() as u64) as usize;
+                            let mut turn = vec![];
+                            for (i, color) in colors.iter().enumerate() {
+                                if i == first || next(2) == 0 {
+                                    turn.push((color.clone(), next(20) as usize));
+                                }
+                            }
+                            turn.into_iter().collect::<CubeCounts>()
+                        })
+                        .collect(),
+                })
+                .collect();
+            let conundrum = Conundrum(games);
+            let printed = conundrum.to_string();
+            let parsed = Conundrum::parse_any(&printed).unwrap();
+            assert_eq!(parsed, conundrum, "{}", printed);
+            assert_eq!(parsed.to_string(), printed);
+        }
+    }
+
+    fn part_one_bag() -> CubeCounts {
+        CubeCounts::from([
+            (CubeColor::Red, 12),
+            (CubeColor::Green, 13),
+            (CubeColor::Blue, 14),
+        ])
+    }
+
+    #[test]
+    fn generated_logs_agree_with_their_ground_truth() {
+        let bag = part_one_bag();
+        let log = GameGenerator::new(&bag, 2023)
+            .unwrap()
+            .turns(1..=10)
+            .generate(2000);
+        let conundrum: Conundrum = log.conundrum.to_string().parse().unwrap();
+        assert_eq!(conundrum, log.conundrum);
+        assert_eq!(conundrum.possible_games(&bag), log.possible);
+        assert_eq!(
+            conundrum.sum_of_possible_game_ids(&bag),
+            log.sum_of_possible_game_ids()
+        );
+        for game in &conundrum.0 {
+            assert!((1..=10).contains(&game.turns.len()));
+            let possible = log.possible.contains(&game.id);
+            assert_eq!(game.smallest_bag().fits(&bag), possible, "{}", game);
+        }
+        // Roughly a third of the games are impossible
+        assert!((1200..1500).contains(&log.possible.len()));
+    }
+
+    #[test]
+    fn generated_logs_only_depend_on_the_seed() {
+        let bag = part_one_bag();
+        let generate = |seed| GameGenerator::new(&bag, seed).unwrap().generate(50);
+        assert_eq!(generate(1), generate(1));
+        assert_ne!(generate(1), generate(2));
+
+        let all_possible = GameGenerator::new(&bag, 1)
+            .unwrap()
+            .impossible_share(0.0)
+            .generate(50);
+        assert_eq!(all_possible.possible, (1..=50).collect::<Vec<usize>>());
+        let none_possible = GameGenerator::new(&bag, 1)
+            .unwrap()
+            .impossible_share(1.0)
+            .turns(0..=0)
+            .generate(50);
+        assert!(none_possible.possible.is_empty());
+        assert!(none_possible
+            .conundrum
+            .0
+            .iter()
+            .all(|game| game.turns.len() == 1));
+
+        let error = GameGenerator::new(&CubeCounts::from([(CubeColor::Red, 0)]), 1).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Empty { what: "bag" });
+    }
+
+    #[test]
+    fn logs_can_be_generated_for_any_colors() {
+        let bag = CubeCounts::from([(yellow(), 3), (black(), 1)]);
+        let log = GameGenerator::new(&bag, 9).unwrap().generate(200);
+        let conundrum = Conundrum::parse_in(
+            &log.conundrum.to_string(),
+            &Palette::new([yellow(), black()]),
+        )
+        .unwrap();
+        assert_eq!(conundrum.possible_games(&bag), log.possible);
+    }
+
+    #[test]
+    fn the_bag_is_estimated_back_from_its_games() {
+        let bag = part_one_bag();
+        // Handfuls of at most 8 of the 39 cubes, so no turn gives the bag away
+        let log = GameGenerator::new(&bag, 3)
+            .unwrap()
+            .impossible_share(0.0)
+            .largest_handful(8)
+            .generate(1000);
+        for turn in log.conundrum.0.iter().flat_map(|game| game.turns.iter()) {
+            let handful: usize = turn.colors().map(|color| turn.get(color)).sum();
+            assert!((1..=8).contains(&handful));
+        }
+        let estimator = BagEstimator::new(&log.conundrum);
+        let estimate = estimator.estimate();
+        assert_eq!(estimate.confidence, 0.95);
+        for color in &estimate.colors {
+            let truth = bag.get(&color.color);
+            let most_drawn = log
+                .conundrum
+                .0
+                .iter()
+                .map(|game| game.smallest_bag().get(&color.color))
+                .max()
+                .unwrap();
+            // The draws alone don't pin the colour down
+            assert!(color.lower > most_drawn);
+            assert!(color.lower <= truth && color.upper.is_some_and(|upper| truth <= upper));
+            assert!(color.estimate.abs_diff(truth) <= 3, "{:?}", color);
+        }
+        assert_eq!(
+            estimator.log_likelihood(&estimate.bag()),
+            estimate.log_likelihood
+        );
+        assert_eq!(estimator.relative_likelihood(&estimate.bag()), 1.0);
+        assert!(estimator.relative_likelihood(&bag) > 0.1);
+
+        let bigger_bag = CubeCounts::from([
+            (CubeColor::Red, 20),
+            (CubeColor::Green, 20),
+            (CubeColor::Blue, 20),
+        ]);
+        assert!(estimator.relative_likelihood(&bigger_bag) < 1e-6);
+        let too_small = CubeCounts::from([(CubeColor::Red, 12), (CubeColor::Green, 13)]);
+        assert_eq!(estimator.log_likelihood(&too_small), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn fewer_games_and_more_confidence_widen_the_intervals() {
+        let bag = part_one_bag();
+        let log = GameGenerator::new(&bag, 3)
+            .unwrap()
+            .impossible_share(0.0)
+            .generate(5);
+        let estimator = BagEstimator::new(&log.conundrum);
+        let width = |confidence| {
+            let estimate = estimator.estimate_with_confidence(confidence).unwrap();
+            estimate
+                .colors
+                .iter()
+                .map(|color| color.upper.unwrap() - color.lower)
+                .sum::<usize>()
+        };
+        assert!(width(0.5) <= width(0.95));
+        assert!(width(0.95) <= width(0.999));
+        assert!(width(0.999) > 0);
+        for color in estimator.estimate().colors {
+            let truth = bag.get(&color.color);
+            assert!(color.lower <= truth && color.upper.is_some_and(|upper| truth <= upper));
+        }
+
+        let error = estimator.estimate_with_confidence(1.0).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 2: confidence has to be between 0 and 1, got 1"
+        );
+    }
+
+    // Few, short games leave the likelihood flat enough that moving one colour at a time gets stuck
+    #[test]
+    fn the_estimate_is_the_most_likely_bag_of_all() {
+        let bag = CubeCounts::from([
+            (CubeColor::Red, 6),
+            (CubeColor::Green, 4),
+            (CubeColor::Blue, 9),
+        ]);
+        let limit = 15;
+        for seed in 0..40 {
+            let log = GameGenerator::new(&bag, seed)
+                .unwrap()
+                .turns(1..=2)
+                .impossible_share(0.0)
+                .generate(4);
+            let estimator = BagEstimator::new(&log.conundrum).limit(limit);
+            let estimate = estimator.estimate();
+            let (mut most_likely, mut most_likely_bag) = (f64::NEG_INFINITY, None);
+            for red in 0..=limit {
+                for green in 0..=limit {
+                    for blue in 0..=limit {
+                        let candidate = CubeCounts::from([
+                            (CubeColor::Red, red),
+                            (CubeColor::Green, green),
+                            (CubeColor::Blue, blue),
+                        ]);
+                        let log_likelihood = estimator.log_likelihood(&candidate);
+                        if log_likelihood > most_likely {
+                            (most_likely, most_likely_bag) = (log_likelihood, Some(candidate));
+                        }
+                    }
+                }
+            }
+            let found = estimator.log_likelihood(&estimate.bag());
+            assert!((found - most_likely).abs() < 1e-9, "seed {}", seed);
+            assert!((estimate.log_likelihood - most_likely).abs() < 1e-9);
+            assert!(estimator.relative_likelihood(&most_likely_bag.unwrap()) <= 1.0 + 1e-9);
+        }
+    }
+
+    #[test]
+    fn colors_the_games_dont_bound_are_left_open() {
+        let conundrum = Conundrum::parse_any("Game 1: 1 red, 1 yellow").unwrap();
+        let palette = Palette::new([CubeColor::Red, yellow(), black()]);
+        let estimate = BagEstimator::with_palette(&conundrum, &palette)
+            .limit(30)
+            .estimate();
+        let black_estimate = &estimate.colors[2];
+        assert_eq!(black_estimate.color, black());
+        assert_eq!(black_estimate.estimate, 0);
+        assert!(estimate.colors[0].upper.is_none());
+
+        let nothing = BagEstimator::new(&Conundrum(vec![])).estimate();
+        assert!(nothing.colors.is_empty());
+    }
 }
@@ -1,28 +1,35 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::day_3_gear_ratios::Schematic;
-    use std::fs;
+    use crate::common::XorShift;
+    use advent_of_code_2023::day_3_gear_ratios::{
+        Adjacency, CellKind, Combine, PartNumber, RenderMode, Schematic, SchematicRules,
+    };
+    use advent_of_code_2023::error::ErrorKind;
+    use advent_of_code_2023::inputs::read_input;
+    use advent_of_code_2023::solution::Solution;
     use std::str::FromStr;
 
     #[test]
     fn example_gives_adequate_part_numbers_and_the_sum_is_correct() {
-        let example = fs::read_to_string("./data/gear_ratios_input_short.txt").unwrap();
+        let example = read_input(3, "example").unwrap();
         let sch: Schematic = Schematic::from_str(example.as_str()).unwrap();
         assert_eq!(sch.get_part_numbers().iter().sum::<usize>(), 4361)
     }
 
     #[test]
     fn example_gives_adequate_gears_and_the_ratios_are_correct() {
-        let example = fs::read_to_string("./data/gear_ratios_input_short.txt").unwrap();
+        let example = read_input(3, "example").unwrap();
         let sch: Schematic = Schematic::from_str(example.as_str()).unwrap();
-        let gears = sch.get_gear_ratios();
+        let gears = sch.get_gear_ratios().unwrap();
         assert_eq!(gears, vec![16345, 451490]);
         assert_eq!(gears.iter().sum::<usize>(), 467835);
     }
 
     #[test]
     fn example_big_gives_adequate_part_numbers_and_the_sum_is_correct() {
-        let example = fs::read_to_string("./data/gear_ratios_input_long.txt").unwrap();
+        let example = read_input(3, "input").unwrap();
         let sch: Schematic = Schematic::from_str(example.as_str()).unwrap();
         assert_ne!(sch.get_part_numbers().iter().sum::<usize>(), 329623); //329623 is not it
         assert_eq!(sch.get_part_numbers().iter().sum::<usize>(), 537732); //329623 is not it
@@ -30,9 +37,382 @@ mod tests {
 
     #[test]
     fn example_big_gives_adequate_part_numbers_and_the_gears_is_correct() {
-        let example = fs::read_to_string("./data/gear_ratios_input_long.txt").unwrap();
+        let example = read_input(3, "input").unwrap();
         let sch: Schematic = Schematic::from_str(example.as_str()).unwrap();
-        let gears = sch.get_gear_ratios();
+        let gears = sch.get_gear_ratios().unwrap();
         assert_eq!(gears.iter().sum::<usize>(), 84883664);
     }
+
+    #[test]
+    fn index_links_symbols_and_numbers_both_ways() {
+        let example = read_input(3, "example").unwrap();
+        let sch: Schematic = example.parse().unwrap();
+        let index = sch.index();
+        assert_eq!(index.numbers().len(), 10);
+        assert_eq!(index.symbols().len(), 6);
+        assert_eq!(
+            index.numbers()[0],
+            PartNumber {
+                number: 467,
+                row: 0,
+                column_start: 0,
+                column_end: 2,
+            }
+        );
+        let star = index.symbols()[0].clone();
+        assert_eq!((star.character, star.row, star.column), ('*', 1, 3));
+        let next_to_star: Vec<usize> = index.numbers_next_to(0).map(|n| n.number).collect();
+        assert_eq!(next_to_star, vec![467, 35]);
+        assert_eq!(index.symbols_next_to(0).collect::<Vec<_>>(), vec![&star]);
+        // 114 and 58 aren't next to any symbol
+        assert_eq!(index.symbols_next_to(1).count(), 0);
+        assert_eq!(index.part_numbers().count(), 8);
+    }
+
+    #[test]
+    fn index_agrees_with_scanning_around_every_symbol() {
+        let mut rng = XorShift::new(0x853C_49E6_748F_EA9B);
+        for _ in 0..200 {
+            let mut rows = vec![];
+            let width = 1 + rng.below(12);
+            for _ in 0..1 + rng.below(6) {
+                let row: String = (0..width)
+                    .map(|_| ['.', '.', '.', '1', '2', '7', '*', '#', '*'][rng.below(9) as usize])
+                    .collect();
+                rows.push(row);
+            }
+            let text: String = rows.iter().map(|row| format!("{}\n", row)).collect();
+            let sch: Schematic = text.parse().unwrap();
+            let mut expected_parts: Vec<PartNumber> = sch
+                .get_symbols()
+                .iter()
+                .flat_map(|symbol| sch.get_numbers_adjacent_to_symbol(symbol))
+                .collect();
+            expected_parts.sort_by_key(|n| (n.row, n.column_start));
+            expected_parts.dedup();
+            let parts: Vec<PartNumber> = sch.index().part_numbers().cloned().collect();
+            assert_eq!(parts, expected_parts, "{}", text);
+            for (i, symbol) in sch.get_symbols().iter().enumerate() {
+                let mut expected = sch.get_numbers_adjacent_to_symbol(symbol);
+                let mut found: Vec<PartNumber> = sch.index().numbers_next_to(i).cloned().collect();
+                expected.sort_by_key(|n| (n.row, n.column_start));
+                found.sort_by_key(|n| (n.row, n.column_start));
+                assert_eq!(found, expected, "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn wide_schematics_are_indexed() {
+        let repeats = 20_000;
+        let text = ["467..114..", "...*......", "..35..633."]
+            .map(|row| row.repeat(repeats) + "\n")
+            .concat();
+        let sch: Schematic = text.parse().unwrap();
+        // 114 and 633 aren't next to the '*'
+        assert_eq!(sch.get_part_numbers().len(), 2 * repeats);
+        assert_eq!(
+            sch.get_part_numbers().iter().sum::<usize>(),
+            (467 + 35) * repeats
+        );
+        assert_eq!(sch.get_gear_ratios().unwrap().len(), repeats);
+        assert_eq!(
+            sch.get_gear_ratios().unwrap().iter().sum::<usize>(),
+            467 * 35 * repeats
+        );
+    }
+
+    #[test]
+    fn numbers_too_big_to_hold_are_an_error() {
+        let error = "..*......................\n1234567890123456789012345\n"
+            .parse::<Schematic>()
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.kind,
+            ErrorKind::InvalidNumber {
+                what: "part number",
+                token: "1234567890123456789012345".to_string()
+            }
+        );
+        assert_eq!(
+            (error.day, error.line, error.column),
+            (Some(3), Some(2), Some(1))
+        );
+    }
+
+    #[test]
+    fn default_rules_are_the_puzzle_ones() {
+        let example = read_input(3, "example").unwrap();
+        let sch = Schematic::parse_with(&example, &SchematicRules::default()).unwrap();
+        assert_eq!(sch.get_part_numbers().iter().sum::<usize>(), 4361);
+        assert_eq!(sch.get_gear_ratios().unwrap(), vec![16345, 451490]);
+    }
+
+    #[test]
+    fn rules_choose_symbols_blank_and_gears() {
+        // '~' is a connector that isn't a symbol, '+' joins three numbers by adding them
+        let text = "12~3__\n___+__\n__4_5_\n";
+        let rules = SchematicRules::default()
+            .blank('_')
+            .symbols(['#'])
+            .gears(['+'])
+            .gear_numbers(3)
+            .combine(Combine::Sum);
+        let sch = Schematic::parse_with(text, &rules).unwrap();
+        assert_eq!(sch.index().symbols().len(), 1);
+        assert_eq!(sch.get_part_numbers(), vec![3, 4, 5]);
+        assert_eq!(sch.get_gear_ratios().unwrap(), vec![12]);
+
+        let custom = rules.combine(Combine::custom(|numbers| {
+            numbers.iter().max().unwrap() * 10
+        }));
+        let sch = Schematic::parse_with(text, &custom).unwrap();
+        assert_eq!(sch.get_gear_ratios().unwrap(), vec![50]);
+
+        // With any symbol, the '~' touches 12 and 3, and the '+' doesn't touch 12
+        let sch = Schematic::parse_with(text, &custom.any_symbol()).unwrap();
+        assert_eq!(sch.get_part_numbers(), vec![12, 3, 4, 5]);
+        assert_eq!(sch.get_gear_ratios().unwrap(), vec![50]);
+    }
+
+    #[test]
+    fn four_neighbour_adjacency_leaves_out_diagonals() {
+        let text = "467.\n...*\n..35\n";
+        let rules = SchematicRules::default().gear_numbers(1);
+        let eight = Schematic::parse_with(text, &rules).unwrap();
+        assert_eq!(eight.get_part_numbers(), vec![467, 35]);
+        let four = Schematic::parse_with(text, &rules.adjacency(Adjacency::Four)).unwrap();
+        assert_eq!(four.get_part_numbers(), vec![35]);
+        assert_eq!(four.get_gear_ratios().unwrap(), vec![35]);
+        let symbol = &four.get_symbols()[0];
+        assert_eq!(four.get_numbers_adjacent_to_symbol(symbol).len(), 1);
+    }
+
+    #[test]
+    fn index_agrees_with_scanning_without_diagonals() {
+        let mut rng = XorShift::new(0x2545_F491_4F6C_DD1D);
+        let rules = SchematicRules::default().adjacency(Adjacency::Four);
+        for _ in 0..200 {
+            let width = 1 + rng.below(10);
+            let text: String = (0..(1 + rng.below(5)) * (width + 1))
+                .map(|i| {
+                    if (i + 1) % (width + 1) == 0 {
+                        '\n'
+                    } else {
+                        ['.', '.', '4', '9', '*', '#'][rng.below(6) as usize]
+                    }
+                })
+                .collect();
+            let sch = Schematic::parse_with(&text, &rules).unwrap();
+            for (i, symbol) in sch.get_symbols().iter().enumerate() {
+                let mut expected = sch.get_numbers_adjacent_to_symbol(symbol);
+                let mut found: Vec<PartNumber> = sch.index().numbers_next_to(i).cloned().collect();
+                expected.sort_by_key(|n| (n.row, n.column_start));
+                found.sort_by_key(|n| (n.row, n.column_start));
+                assert_eq!(found, expected, "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn digits_cant_be_symbols_or_the_blank() {
+        for rules in [
+            SchematicRules::default().blank('0'),
+            SchematicRules::default().symbols(['#', '7']),
+            SchematicRules::default().gears(['1']),
+            SchematicRules::default().gears(['.']),
+        ] {
+            let error = Schematic::parse_with("1.\n", &rules).err().unwrap();
+            assert!(matches!(error.kind, ErrorKind::Invalid(_)), "{:?}", rules);
+        }
+    }
+
+    #[test]
+    fn schematics_print_as_they_were_read() {
+        let example = read_input(3, "example").unwrap();
+        let sch: Schematic = example.parse().unwrap();
+        // Every row ends in a newline, the last one of the input file included
+        assert_eq!(sch.to_string(), format!("{}\n", example.trim_end()));
+    }
+
+    #[test]
+    fn plain_rendering_marks_every_cell_under_its_row() {
+        let text = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n";
+        let sch: Schematic = text.parse().unwrap();
+        assert_eq!(
+            sch.renderer().render(),
+            [
+                "467..114..",
+                "ppp  xxx",
+                "...*......",
+                "   g",
+                "..35..633.",
+                "  pp  ppp",
+                "......#...",
+                "      s",
+                "617*......",
+                "ppps",
+                "",
+            ]
+            .join("\n")
+        );
+        assert_eq!(sch.renderer().kinds()[(4, 3)], CellKind::Symbol);
+    }
+
+    #[test]
+    fn windows_only_render_the_cells_around_a_coordinate() {
+        let text = "467..114..\n...*......\n..35..633.\n";
+        let sch: Schematic = text.parse().unwrap();
+        assert_eq!(sch.renderer().around(0, 6, 1).render(), "114\nxxx\n...\n\n");
+        // The window stops at the edges of the schematic
+        assert_eq!(sch.renderer().around(2, 0, 1).render(), "..\n\n..\n\n");
+        assert_eq!(sch.renderer().around(5, 50, 2).render(), "");
+    }
+
+    #[test]
+    fn ansi_and_html_rendering_highlight_runs_of_cells() {
+        let text = "1.2\n*<.\n";
+        let sch: Schematic = text.parse().unwrap();
+        assert_eq!(
+            sch.renderer().mode(RenderMode::Ansi).render(),
+            "\x1b[32m1\x1b[0m.\x1b[32m2\x1b[0m\n\x1b[36m*<\x1b[0m.\n"
+        );
+        assert_eq!(
+            sch.renderer().mode(RenderMode::Html).render(),
+            "<pre class=\"schematic\">\n\
+             <span class=\"part\">1</span>.<span class=\"part\">2</span>\n\
+             <span class=\"symbol\">*&lt;</span>.\n\
+             </pre>\n"
+        );
+    }
+
+    #[test]
+    fn rendering_follows_the_rules() {
+        let text = "12~3__\n___+__\n__4_5_\n";
+        let rules = SchematicRules::default()
+            .blank('_')
+            .symbols(['#'])
+            .gears(['+'])
+            .gear_numbers(3);
+        let sch = Schematic::parse_with(text, &rules).unwrap();
+        assert_eq!(
+            sch.renderer().render(),
+            "12~3__\nxx p\n___+__\n   g\n__4_5_\n  p p\n"
+        );
+    }
+
+    #[test]
+    fn example_graph_has_a_machine_per_symbol() {
+        let example = read_input(3, "example").unwrap();
+        let sch: Schematic = example.parse().unwrap();
+        let graph = sch.graph();
+        let sums: Vec<usize> = graph.machines().iter().map(|m| m.sum().unwrap()).collect();
+        assert_eq!(sums, vec![467 + 35, 633, 617, 592, 664, 755 + 598]);
+        let largest = graph.largest_machine().unwrap().unwrap();
+        assert_eq!(largest.sum().unwrap(), 1353);
+        assert_eq!(
+            largest
+                .symbols
+                .iter()
+                .map(|s| (s.character, s.row, s.column))
+                .collect::<Vec<_>>(),
+            vec![('*', 8, 5)]
+        );
+        assert_eq!(graph.edges().count(), 8);
+        assert!(graph.shared_numbers().is_empty());
+        let orphans: Vec<usize> = graph.orphans().iter().map(|n| n.number).collect();
+        assert_eq!(orphans, vec![114, 58]);
+    }
+
+    #[test]
+    fn shared_numbers_join_symbols_into_one_machine() {
+        let text = "..*....7\n.12..3..\n...#..4$\n";
+        let sch: Schematic = text.parse().unwrap();
+        let graph = sch.graph();
+        let machines = graph.machines();
+        assert_eq!(machines.len(), 2);
+        assert_eq!(machines[0].symbols.len(), 2);
+        assert_eq!(
+            machines[0]
+                .numbers
+                .iter()
+                .map(|n| n.number)
+                .collect::<Vec<_>>(),
+            vec![12]
+        );
+        assert_eq!(machines[1].sum().unwrap(), 4);
+        let shared: Vec<usize> = graph.shared_numbers().iter().map(|n| n.number).collect();
+        assert_eq!(shared, vec![12]);
+        let orphans: Vec<usize> = graph.orphans().iter().map(|n| n.number).collect();
+        assert_eq!(orphans, vec![7, 3]);
+        assert_eq!(graph.largest_machine().unwrap(), Some(machines[0].clone()));
+    }
+
+    #[test]
+    fn largest_machine_is_the_first_of_a_tie_and_none_without_symbols() {
+        let sch: Schematic = "5*..*5\n".parse().unwrap();
+        let largest = sch.graph().largest_machine().unwrap().unwrap();
+        assert_eq!(largest.symbols[0].column, 1);
+        let sch: Schematic = "12..3\n".parse().unwrap();
+        assert_eq!(sch.graph().largest_machine().unwrap(), None);
+        assert_eq!(sch.graph().orphans().len(), 2);
+    }
+
+    #[test]
+    fn machines_cover_every_part_number_once() {
+        let input = read_input(3, "input").unwrap();
+        let sch: Schematic = input.parse().unwrap();
+        let graph = sch.graph();
+        let machines = graph.machines();
+        assert_eq!(
+            machines.iter().map(|m| m.sum().unwrap()).sum::<usize>(),
+            sch.get_part_numbers().iter().sum::<usize>()
+        );
+        assert_eq!(
+            machines.iter().map(|m| m.symbols.len()).sum::<usize>(),
+            sch.index().symbols().len()
+        );
+        assert_eq!(
+            graph.orphans().len() + sch.get_part_numbers().len(),
+            sch.index().numbers().len()
+        );
+    }
+
+    #[test]
+    fn gear_ratios_too_big_to_hold_are_an_error() {
+        let sch: Schematic = "9999999999999*9999999999999\n".parse().unwrap();
+        let error = sch.get_gear_ratios().err().unwrap();
+        assert_eq!(error.kind, ErrorKind::Overflow { what: "gear ratio" });
+        assert_eq!(
+            (error.day, error.line, error.column),
+            (Some(3), Some(1), Some(14))
+        );
+        assert!(sch.part_two().is_err());
+        // Adding them instead fits
+        let rules = SchematicRules::default().combine(Combine::Sum);
+        let sch = Schematic::parse_with("9999999999999*9999999999999\n", &rules).unwrap();
+        assert_eq!(sch.get_gear_ratios().unwrap(), vec![19999999999998]);
+    }
+
+    #[test]
+    fn part_number_sums_too_big_to_hold_are_an_error() {
+        let sch: Schematic = "18446744073709551615#1\n".parse().unwrap();
+        let error = sch.part_one().err().unwrap();
+        assert_eq!(
+            error.kind,
+            ErrorKind::Overflow {
+                what: "sum of part numbers"
+            }
+        );
+        let graph = sch.graph();
+        let error = graph.machines()[0].sum().err().unwrap();
+        assert_eq!(
+            error.kind,
+            ErrorKind::Overflow {
+                what: "sum of a machine"
+            }
+        );
+        assert!(graph.largest_machine().is_err());
+    }
 }
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod test_simple_struct_parsing {
     use advent_of_code_2023::day_4_scratch_cards::{ScratchCard, ScratchPile};
-    use std::fs;
+    use advent_of_code_2023::inputs::read_input;
 
     #[test]
     fn single_scratchcard_parsed_correctly() {
@@ -12,7 +12,7 @@ mod test_simple_struct_parsing {
     }
     #[test]
     fn scratchpile_example_parsed_correctly() {
-        let example = fs::read_to_string("./data/scratchcards_input_short.txt").unwrap();
+        let example = read_input(4, "example").unwrap();
         let scratch: ScratchPile = example.parse().unwrap();
         assert_eq!(scratch.matches_double(), 13);
     }
@@ -20,23 +20,356 @@ mod test_simple_struct_parsing {
 
 mod test_challenge_parsing {
     use advent_of_code_2023::day_4_scratch_cards::{ScratchCard, ScratchPile};
-    use std::fs;
+    use advent_of_code_2023::inputs::read_input;
     #[test]
     fn scratchpile_challenge_1_parsed_correctly() {
-        let example = fs::read_to_string("./data/scratchcards_input_long.txt").unwrap();
+        let example = read_input(4, "input").unwrap();
         let scratch: ScratchPile = example.parse().unwrap();
         assert_eq!(scratch.matches_double(), 22193);
     }
     #[test]
     fn scratchpile_challenge_2_example_parsed_correctly() {
-        let example = fs::read_to_string("./data/scratchcards_input_short.txt").unwrap();
+        let example = read_input(4, "example").unwrap();
         let scratch: ScratchPile = example.parse().unwrap();
         assert_eq!(scratch.worth(), 30);
     }
     #[test]
     fn scratchpile_challenge_2_long_example_parsed_correctly() {
-        let example = fs::read_to_string("./data/scratchcards_input_long.txt").unwrap();
+        let example = read_input(4, "input").unwrap();
         let scratch: ScratchPile = example.parse().unwrap();
         assert_eq!(scratch.worth(), 5625994);
     }
 }
+mod test_streaming {
+    use advent_of_code_2023::day_4_scratch_cards::{PileEvaluator, ScratchPile};
+    use advent_of_code_2023::error::ErrorKind;
+    use advent_of_code_2023::inputs::read_input;
+    use advent_of_code_2023::solution::get_day;
+    use num_bigint::BigUint;
+    use std::io::Cursor;
+
+    #[test]
+    fn streaming_gives_the_same_totals_as_the_whole_pile() {
+        for variant in ["example", "input"] {
+            let input = read_input(4, variant).unwrap();
+            let pile: ScratchPile = input.parse().unwrap();
+            let totals = ScratchPile::totals_from(Cursor::new(&input)).unwrap();
+            assert_eq!(totals, pile.totals());
+            assert_eq!(totals.cards, input.lines().count());
+            assert_eq!(totals.points_usize().unwrap(), pile.matches_double());
+            assert_eq!(totals.instances_usize().unwrap(), pile.worth());
+        }
+    }
+
+    #[test]
+    fn streaming_normalises_lines_like_the_inputs() {
+        let input = read_input(4, "example").unwrap();
+        let pile: ScratchPile = input.parse().unwrap();
+        let messy = format!(
+            "\u{feff}{}",
+            input.replace(": ", ":\t").replace('\n', " \r\n")
+        );
+        let totals = ScratchPile::totals_from(Cursor::new(messy)).unwrap();
+        assert_eq!(totals, pile.totals());
+    }
+
+    // Whatever the day accepts, streaming accepts too, with the same answer or on the same line
+    #[test]
+    fn streaming_agrees_with_solving_the_day() {
+        let cards = "Card 1: 41 48 | 41 9\nCard 2: 13 32 | 17 1\n";
+        let inputs = [
+            format!("{}\n", cards),
+            format!("{}\n \t\r\n\n", cards),
+            cards.replace('\n', "\r"),
+            format!("\u{feff}{}", cards.replace('\n', "\r\n")),
+            cards.replace('\n', "\n\n"),
+            format!("{}Card 3: 1 | 1é\n", cards),
+        ];
+        for input in inputs {
+            let streamed = ScratchPile::totals_from(Cursor::new(&input));
+            match get_day(4).unwrap().solve(2, &input) {
+                Ok(answer) => {
+                    let instances = streamed.unwrap().instances;
+                    assert_eq!(instances.to_string(), answer.to_string(), "{:?}", input);
+                }
+                Err(error) => {
+                    let streamed = streamed.unwrap_err();
+                    assert_eq!(streamed.kind, error.kind, "{:?}", input);
+                    assert_eq!((streamed.line, streamed.column), (error.line, error.column));
+                }
+            }
+        }
+        assert!(ScratchPile::totals_from(Cursor::new(cards.replace('\n', "\r"))).is_ok());
+        assert!(ScratchPile::totals_from(Cursor::new(format!("{}\n", cards))).is_ok());
+    }
+
+    #[test]
+    fn copies_past_the_end_of_the_pile_are_dropped() {
+        let input = "Card 1: 1 2 3 | 1 2 3\nCard 2: 4 | 5\n";
+        let totals = ScratchPile::totals_from(Cursor::new(input)).unwrap();
+        assert_eq!(totals.points, BigUint::from(4u32));
+        // Card 2 gets a copy from card 1, the other two would be past the end
+        assert_eq!(totals.instances, BigUint::from(3u32));
+    }
+
+    #[test]
+    fn instances_grow_past_any_machine_integer() {
+        // Every card wins copies of the next two, so each has one more than the two before put together
+        let lines: String = (1..=200)
+            .map(|id| format!("Card {}: 7 8 | 7 8 9\n", id))
+            .collect();
+        let totals = ScratchPile::totals_from(Cursor::new(lines)).unwrap();
+        let (mut before, mut last) = (BigUint::from(0u32), BigUint::from(0u32));
+        let mut expected = BigUint::from(0u32);
+        for _ in 0..200 {
+            let copies = BigUint::from(1u32) + &before + &last;
+            expected += &copies;
+            (before, last) = (last, copies);
+        }
+        assert_eq!(totals.instances, expected);
+        assert_eq!(totals.points, BigUint::from(400u32));
+        assert_eq!(
+            totals.instances_usize().unwrap_err().kind,
+            ErrorKind::Overflow {
+                what: "card instances"
+            }
+        );
+    }
+
+    #[test]
+    fn evaluators_can_be_fed_match_counts() {
+        let mut evaluator = PileEvaluator::new();
+        for match_count in [4, 2, 2, 1, 0, 0] {
+            evaluator.push_match_count(match_count);
+        }
+        let totals = evaluator.finish();
+        assert_eq!(totals.points, BigUint::from(13u32));
+        assert_eq!(totals.instances, BigUint::from(30u32));
+    }
+
+    #[test]
+    fn broken_cards_fail_on_their_line() {
+        let input = "Card 1: 1 2 | 1 2\nCard 2 1 2 | 1 2\n";
+        let error = ScratchPile::totals_from(Cursor::new(input)).unwrap_err();
+        assert_eq!((error.day, error.line), (Some(4), Some(2)));
+    }
+}
+
+mod test_rules {
+    use advent_of_code_2023::day_4_scratch_cards::{
+        CappedPoints, CopyRule, DoublingPoints, EveryKthCard, FibonacciPoints, LinearPoints,
+        NextCards, PointsRule, PreviousCards, ScratchPile, WrapAround,
+    };
+    use advent_of_code_2023::error::ErrorKind;
+    use advent_of_code_2023::inputs::read_input;
+
+    fn example() -> ScratchPile {
+        read_input(4, "example").unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn built_in_rules_are_the_puzzle_ones() {
+        for variant in ["example", "input"] {
+            let pile: ScratchPile = read_input(4, variant).unwrap().parse().unwrap();
+            assert_eq!(
+                pile.points_with(&DoublingPoints).unwrap(),
+                pile.matches_double()
+            );
+            assert_eq!(pile.instances_with(&NextCards).unwrap(), pile.worth());
+        }
+    }
+
+    #[test]
+    fn other_scoring_curves() {
+        // The example's cards have 4, 2, 2, 1, 0 and 0 matches
+        let pile = example();
+        assert_eq!(
+            pile.points_with(&LinearPoints { per_match: 3 }).unwrap(),
+            27
+        );
+        assert_eq!(pile.points_with(&FibonacciPoints).unwrap(), 5 + 2 + 2 + 1);
+        let curve: Vec<Option<usize>> = (0..6).map(|n| FibonacciPoints.points(n)).collect();
+        assert_eq!(curve, [0, 1, 2, 3, 5, 8].map(Some));
+        let capped = CappedPoints {
+            rule: DoublingPoints,
+            cap: 4,
+        };
+        assert_eq!(pile.points_with(&capped).unwrap(), 4 + 2 + 2 + 1);
+        assert_eq!(capped.points(200), Some(4));
+        let rules: Vec<Box<dyn PointsRule>> = vec![Box::new(DoublingPoints), Box::new(capped)];
+        let totals: Vec<usize> = rules
+            .iter()
+            .map(|rule| pile.points_with(rule.as_ref()).unwrap())
+            .collect();
+        assert_eq!(totals, vec![13, 9]);
+    }
+
+    #[test]
+    fn other_copy_rules() {
+        let pile = example();
+        assert_eq!(
+            pile.instances_with(&PreviousCards).unwrap(),
+            6 + 3 + 2 + 1 + 1 + 1
+        );
+        assert_eq!(
+            pile.instances_with(&EveryKthCard::new(2)).unwrap(),
+            1 + 1 + 2 + 2 + 4 + 4
+        );
+        assert_eq!(EveryKthCard::new(0).copies(0, 2, 6), vec![1, 2]);
+        // Nothing in the example reaches past the last card
+        assert_eq!(pile.instances_with(&WrapAround).unwrap(), 30);
+        assert_eq!(WrapAround.copies(4, 3, 6), vec![5, 0, 1]);
+    }
+
+    #[test]
+    fn wrapping_around_gives_copies_of_the_first_cards() {
+        let pile: ScratchPile = "Card 1: 1 | 2\nCard 2: 1 | 1\n".parse().unwrap();
+        assert_eq!(pile.instances_with(&NextCards).unwrap(), 2);
+        assert_eq!(pile.instances_with(&WrapAround).unwrap(), 3);
+    }
+
+    #[test]
+    fn copy_rules_that_loop_are_an_error() {
+        // Cards 2, 3 and 4 win each other round and round, and card 4 gives card 1 endless copies too
+        let pile: ScratchPile = "Card 1: 1 | 2\nCard 2: 1 | 1\nCard 3: 1 | 1\nCard 4: 1 2 | 1 2\n"
+            .parse()
+            .unwrap();
+        let error = pile.instances_with(&WrapAround).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid(
+                "copies of card 1 never stop coming, the copy rule loops".to_string()
+            )
+        );
+        assert_eq!((error.day, error.line), (Some(4), Some(1)));
+    }
+
+    #[test]
+    fn points_that_dont_fit_are_an_error() {
+        let pile: ScratchPile = "Card 1: 1 | 2\nCard 2: 1 2 | 1 2\n".parse().unwrap();
+        let error = pile
+            .points_with(&LinearPoints {
+                per_match: usize::MAX,
+            })
+            .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Overflow { what: "points" });
+        assert_eq!(error.line, Some(2));
+        assert_eq!(DoublingPoints.points(65), None);
+    }
+}
+
+mod test_trace {
+    use advent_of_code_2023::day_4_scratch_cards::{
+        Contribution, CopyRule, PreviousCards, ScratchPile,
+    };
+    use advent_of_code_2023::inputs::read_input;
+
+    // A copy of the next card for every match, none past the end of the pile
+    struct NextCardOverAndOver;
+
+    impl CopyRule for NextCardOverAndOver {
+        fn copies(&self, card: usize, match_count: usize, pile_len: usize) -> Vec<usize> {
+            if card + 1 < pile_len {
+                vec![card + 1; match_count]
+            } else {
+                vec![]
+            }
+        }
+    }
+
+    fn example() -> ScratchPile {
+        read_input(4, "example").unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn example_trace_explains_every_card() {
+        let trace = example().trace().unwrap();
+        let instances: Vec<usize> = trace.cards.iter().map(|card| card.instances).collect();
+        assert_eq!(instances, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(trace.instances().unwrap(), 30);
+        let card_1 = trace.card(1).unwrap();
+        assert_eq!(card_1.match_count, 4);
+        assert_eq!(card_1.matches, vec![83, 86, 17, 48]);
+        assert!(card_1.contributions.is_empty());
+        assert_eq!(
+            trace.card(5).unwrap().contributions,
+            vec![
+                Contribution { card: 1, copies: 1 },
+                Contribution { card: 3, copies: 4 },
+                Contribution { card: 4, copies: 8 },
+            ]
+        );
+        assert_eq!(trace.card(7), None);
+    }
+
+    #[test]
+    fn every_card_has_its_contributions_plus_the_original() {
+        let pile: ScratchPile = read_input(4, "input").unwrap().parse().unwrap();
+        let trace = pile.trace().unwrap();
+        assert_eq!(trace.instances().unwrap(), pile.worth());
+        for card in &trace.cards {
+            let copies: usize = card.contributions.iter().map(|c| c.copies).sum();
+            assert_eq!(card.instances, copies + 1, "card {}", card.id);
+            assert!(card.contributions.iter().all(|c| c.card < card.id));
+        }
+    }
+
+    #[test]
+    fn traces_follow_the_copy_rule() {
+        let trace = example().trace_with(&PreviousCards).unwrap();
+        assert_eq!(
+            trace.card(1).unwrap().contributions,
+            vec![
+                Contribution { card: 2, copies: 3 },
+                Contribution { card: 3, copies: 2 },
+            ]
+        );
+        assert_eq!(trace.instances().unwrap(), 14);
+    }
+
+    #[test]
+    fn copies_of_the_same_card_add_up_to_one_contribution() {
+        let trace = example().trace_with(&NextCardOverAndOver).unwrap();
+        // Card 1's 4 matches give card 2 four copies, and each of its 5 instances gives card 3 two
+        assert_eq!(
+            trace.card(2).unwrap().contributions,
+            vec![Contribution { card: 1, copies: 4 }]
+        );
+        assert_eq!(trace.card(2).unwrap().instances, 5);
+        assert_eq!(
+            trace.card(3).unwrap().contributions,
+            vec![Contribution {
+                card: 2,
+                copies: 10
+            }]
+        );
+    }
+
+    #[test]
+    fn traces_export_as_csv_and_json() {
+        let pile: ScratchPile = "Card 1: 1 2 | 2 1 3\nCard 2: 5 | 5\nCard 3: 1 | 2\n"
+            .parse()
+            .unwrap();
+        let trace = pile.trace().unwrap();
+        assert_eq!(
+            trace.to_csv(),
+            "card,match_count,matches,instances,copies_from\n\
+             1,2,2 1,1,\n\
+             2,1,5,2,1:1\n\
+             3,0,,4,1:1 2:2\n"
+        );
+        assert_eq!(
+            trace.to_json().unwrap(),
+            "{\n  \"instances\": 7,\n  \"cards\": [\n    \
+             {\"card\": 1, \"match_count\": 2, \"matches\": [2, 1], \"instances\": 1, \"copies_from\": []},\n    \
+             {\"card\": 2, \"match_count\": 1, \"matches\": [5], \"instances\": 2, \"copies_from\": [{\"card\": 1, \"copies\": 1}]},\n    \
+             {\"card\": 3, \"match_count\": 0, \"matches\": [], \"instances\": 4, \"copies_from\": [{\"card\": 1, \"copies\": 1}, {\"card\": 2, \"copies\": 2}]}\n  \
+             ]\n}\n"
+        );
+        let empty: ScratchPile = "".parse().unwrap();
+        assert_eq!(
+            empty.trace().unwrap().to_json().unwrap(),
+            "{\n  \"instances\": 0,\n  \"cards\": []\n}\n"
+        );
+    }
+}
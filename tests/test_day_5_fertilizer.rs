@@ -1,8 +1,8 @@
 #[cfg(test)]
 mod test_challenge_parsing {
     use advent_of_code_2023::day_5_fertilizer::{Almanac, MapRange, MapRangeList};
+    use advent_of_code_2023::inputs::read_input;
     use itertools::Itertools;
-    use std::fs;
 
     #[test]
     fn parse_single_map() {
@@ -25,40 +25,91 @@ mod test_challenge_parsing {
 
     #[test]
     fn parse_almanac_indexes_correctly() {
-        let almanac_str = fs::read_to_string("./data/fertilizer_almanac_input_short.txt").unwrap();
+        let almanac_str = read_input(5, "example").unwrap();
         let almanac: Almanac = almanac_str.parse().unwrap();
-        let locations = almanac.get_seeds_as_individual_ranges();
+        let locations = almanac.get_seeds_as_individual_ranges().unwrap();
         let locations_vec = locations.iter().cloned().collect_vec();
         assert_eq!(
             locations_vec,
             vec![13_isize..15_isize, 55_isize..56_isize, 79_isize..80_isize,]
         );
         println!("Passed assertion 1");
-        let closest = almanac.get_lowest_individual_seed_location();
+        let closest = almanac.get_lowest_individual_seed_location().unwrap();
         assert_eq!(closest, 35_isize);
     }
 
     #[test]
     fn parse_almanac_indexes_correctly2() {
-        let almanac_str = fs::read_to_string("./data/fertilizer_almanac_input_long.txt").unwrap();
+        let almanac_str = read_input(5, "input").unwrap();
         let almanac: Almanac = almanac_str.parse().unwrap();
-        let closest = almanac.get_lowest_individual_seed_location();
+        let closest = almanac.get_lowest_individual_seed_location().unwrap();
         assert_eq!(closest, 84470622_isize);
     }
 
     #[test]
     fn almanac_gets_location_right_with_seed_ranges() {
-        let almanac_str = fs::read_to_string("./data/fertilizer_almanac_input_short.txt").unwrap();
+        let almanac_str = read_input(5, "example").unwrap();
         let almanac: Almanac = almanac_str.parse().unwrap();
-        let closest = almanac.get_lowest_seed_ranges_locations();
+        let closest = almanac.get_lowest_seed_ranges_locations().unwrap();
         assert_eq!(closest, 46);
     }
 
     #[test]
     fn almanac_gets_location_right_with_seed_ranges_long() {
-        let almanac_str = fs::read_to_string("./data/fertilizer_almanac_input_long.txt").unwrap();
+        let almanac_str = read_input(5, "input").unwrap();
         let almanac: Almanac = almanac_str.parse().unwrap();
-        let closest = almanac.get_lowest_seed_ranges_locations();
+        let closest = almanac.get_lowest_seed_ranges_locations().unwrap();
         assert_eq!(closest, 26714516);
     }
 }
+
+#[cfg(test)]
+mod test_seed_lists {
+    use advent_of_code_2023::day_5_fertilizer::Almanac;
+    use advent_of_code_2023::error::ErrorKind;
+    use advent_of_code_2023::inputs::read_input;
+    use advent_of_code_2023::solution::Solution;
+
+    fn with_seeds(seeds: &str) -> String {
+        let example = read_input(5, "example").unwrap();
+        let (_, maps) = example.split_once('\n').unwrap();
+        format!("seeds:{}\n{}", seeds, maps)
+    }
+
+    #[test]
+    fn seed_lists_without_a_length_for_every_start_are_an_error() {
+        let error = with_seeds(" 79 14 55").parse::<Almanac>().err().unwrap();
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid(
+                "the seeds come in pairs of a start and a length, got 3 numbers".to_string()
+            )
+        );
+        assert_eq!((error.day, error.line), (Some(5), Some(1)));
+    }
+
+    #[test]
+    fn empty_seed_lists_are_an_error() {
+        let error = with_seeds("").parse::<Almanac>().err().unwrap();
+        assert_eq!(error.kind, ErrorKind::Empty { what: "seed list" });
+        assert_eq!(error.line, Some(1));
+    }
+
+    #[test]
+    fn seed_ranges_with_no_seeds_leave_no_location() {
+        let almanac: Almanac = with_seeds(" 79 0").parse().unwrap();
+        assert!(almanac.part_one().is_ok());
+        assert_eq!(
+            almanac.part_two().err().unwrap().kind,
+            ErrorKind::Empty {
+                what: "seed locations"
+            }
+        );
+        let almanac: Almanac = with_seeds(" 9223372036854775807 2").parse().unwrap();
+        assert!(almanac.part_one().is_err());
+        assert_eq!(
+            almanac.part_two().err().unwrap().kind,
+            ErrorKind::Overflow { what: "seed range" }
+        );
+    }
+}
@@ -1,11 +1,11 @@
 #[cfg(test)]
 mod test_challenge_parsing {
     use advent_of_code_2023::day_6_wait_for_it::{Document, RaceRecord};
-    use std::fs;
+    use advent_of_code_2023::inputs::read_input;
 
     #[test]
     fn parse_document() {
-        let document_str = fs::read_to_string("./data/waitforit_input_short.txt").unwrap();
+        let document_str = read_input(6, "example").unwrap();
         let doc: Document = document_str.parse().unwrap();
         let race_records = doc.into_vec();
         assert_eq!(
@@ -41,7 +41,7 @@ mod test_challenge_parsing {
 
     #[test]
     fn find_ways_to_beat() {
-        let document_str = fs::read_to_string("./data/waitforit_input_short.txt").unwrap();
+        let document_str = read_input(6, "example").unwrap();
         let doc: Document = document_str.parse().unwrap();
         let ways_to_beat = doc.ways_to_beat();
         assert_eq!(ways_to_beat, vec![4, 8, 9]);
@@ -52,7 +52,7 @@ mod test_challenge_parsing {
 
     #[test]
     fn find_ways_to_beat_input_mid() {
-        let document_str = fs::read_to_string("./data/waitforit_input_mid.txt").unwrap();
+        let document_str = read_input(6, "input").unwrap();
         let doc: Document = document_str.parse().unwrap();
         let product = doc.product_of_ways_to_beat();
         assert_eq!(product, 140220);
@@ -60,7 +60,7 @@ mod test_challenge_parsing {
 
     #[test]
     fn find_ways_to_beat_input_long() {
-        let document_str = fs::read_to_string("./data/waitforit_input_long.txt").unwrap();
+        let document_str = read_input(6, "kerned_example").unwrap();
         let doc: Document = document_str.parse().unwrap();
         let product = doc.product_of_ways_to_beat();
         assert_eq!(product, 71503);
@@ -68,7 +68,7 @@ mod test_challenge_parsing {
 
     #[test]
     fn find_ways_to_beat_input_much_long() {
-        let document_str = fs::read_to_string("./data/waitforit_input_much_long.txt").unwrap();
+        let document_str = read_input(6, "kerned_input").unwrap();
         let doc: Document = document_str.parse().unwrap();
         let product = doc.product_of_ways_to_beat();
         assert_eq!(product, 39570185);
@@ -1,8 +1,8 @@
 #[cfg(test)]
 mod test_challenge_parsing {
     use advent_of_code_2023::day_7_camel_cards::{CamelCard, CamelHand, CamelHandSet};
+    use advent_of_code_2023::inputs::read_input;
     use itertools::Itertools;
-    use std::fs;
 
     #[test]
     fn parse_card_and_sort_correctly() {
@@ -65,19 +65,13 @@ mod test_challenge_parsing {
 
     #[test]
     fn short_exercise_works_as_expected() {
-        let camelset: CamelHandSet = fs::read_to_string("./data/camel_cards_input_short.txt")
-            .unwrap()
-            .parse()
-            .unwrap();
+        let camelset: CamelHandSet = read_input(7, "example").unwrap().parse().unwrap();
         assert_eq!(camelset.total_winnings(), 5905);
     }
 
     #[test]
     fn big_exercise_works_as_expected() {
-        let camelset: CamelHandSet = fs::read_to_string("./data/camel_cards_input_long.txt")
-            .unwrap()
-            .parse()
-            .unwrap();
+        let camelset: CamelHandSet = read_input(7, "input").unwrap().parse().unwrap();
         assert_eq!(camelset.total_winnings(), 255632664);
     }
 }
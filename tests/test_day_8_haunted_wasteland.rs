@@ -1,12 +1,12 @@
 #[cfg(test)]
 mod test_challenge_parsing {
     use advent_of_code_2023::day_8_haunted_wasteland::{factorize, Direction, GhostMap};
+    use advent_of_code_2023::inputs::read_input;
     use itertools::Itertools;
-    use std::fs;
 
     #[test]
     fn parse_ghost_card_correctly() {
-        let buffer = fs::read_to_string("./data/haunted_wasteland_input_short.txt").unwrap();
+        let buffer = read_input(8, "example").unwrap();
         let ghost_map: GhostMap = buffer.parse().unwrap();
         assert_eq!(
             ghost_map.directions,
@@ -34,48 +34,60 @@ mod test_challenge_parsing {
 
     #[test]
     fn ghost_card_takes_two_steps_to_zzz_from_aaa() {
-        let buffer = fs::read_to_string("./data/haunted_wasteland_input_short.txt").unwrap();
+        let buffer = read_input(8, "example").unwrap();
         let ghost_map: GhostMap = buffer.parse().unwrap();
         assert_eq!(
-            ghost_map.walk_from_key("AAA".to_string(), "ZZZ".to_string()),
+            ghost_map
+                .walk_from_key("AAA".to_string(), "ZZZ".to_string())
+                .unwrap(),
             2
         );
     }
 
     #[test]
     fn ghost_card_takes_six_steps_to_zzz_from_aaa() {
-        let buffer = fs::read_to_string("./data/haunted_wasteland_input_mini.txt").unwrap();
+        let buffer = read_input(8, "repeating_example").unwrap();
         let ghost_map: GhostMap = buffer.parse().unwrap();
         assert_eq!(
-            ghost_map.walk_from_key("AAA".to_string(), "ZZZ".to_string()),
+            ghost_map
+                .walk_from_key("AAA".to_string(), "ZZZ".to_string())
+                .unwrap(),
             6
         );
     }
 
     #[test]
     fn ghost_card_long() {
-        let buffer = fs::read_to_string("./data/haunted_wasteland_input_long.txt").unwrap();
+        let buffer = read_input(8, "input").unwrap();
         let ghost_map: GhostMap = buffer.parse().unwrap();
         assert_eq!(
-            ghost_map.walk_from_key("AAA".to_string(), "ZZZ".to_string()),
+            ghost_map
+                .walk_from_key("AAA".to_string(), "ZZZ".to_string())
+                .unwrap(),
             16579
         );
     }
 
     #[test]
     fn ghost_card_short_spacetime() {
-        let buffer =
-            fs::read_to_string("./data/haunted_wasteland_spacetime_input_short.txt").unwrap();
+        let buffer = read_input(8, "ghost_example").unwrap();
         let ghost_map: GhostMap = buffer.parse().unwrap();
-        assert_eq!(ghost_map.walk_across_spacetime_from_ending('A', 'Z'), 6);
+        assert_eq!(
+            ghost_map
+                .walk_across_spacetime_from_ending('A', 'Z')
+                .unwrap(),
+            6
+        );
     }
 
     #[test]
     fn ghost_card_long_spacetime() {
-        let buffer = fs::read_to_string("./data/haunted_wasteland_input_long.txt").unwrap();
+        let buffer = read_input(8, "input").unwrap();
         let ghost_map: GhostMap = buffer.parse().unwrap();
         assert_eq!(
-            ghost_map.walk_across_spacetime_from_ending('A', 'Z'),
+            ghost_map
+                .walk_across_spacetime_from_ending('A', 'Z')
+                .unwrap(),
             12927600769609
         );
     }
@@ -94,3 +106,65 @@ mod test_challenge_parsing {
         assert_eq!(factorize(270), vec![2, 3, 3, 3, 5]);
     }
 }
+
+#[cfg(test)]
+mod test_missing_nodes {
+    use advent_of_code_2023::day_8_haunted_wasteland::GhostMap;
+    use advent_of_code_2023::error::ErrorKind;
+    use advent_of_code_2023::solution::Solution;
+
+    #[test]
+    fn maps_without_start_or_end_nodes_are_an_error_in_both_parts() {
+        let ghost_map: GhostMap = "LR\n\nBBB = (BBB, BBB)\n".parse().unwrap();
+        assert_eq!(
+            ghost_map.part_one().err().unwrap().kind,
+            ErrorKind::UnknownToken {
+                what: "node",
+                token: "AAA".to_string()
+            }
+        );
+        assert_eq!(
+            ghost_map.part_two().err().unwrap().kind,
+            ErrorKind::UnknownToken {
+                what: "node ending",
+                token: "A".to_string()
+            }
+        );
+        let ghost_map: GhostMap = "LR\n\nAAA = (AAA, AAA)\n".parse().unwrap();
+        assert_eq!(
+            ghost_map
+                .walk_across_spacetime_from_ending('A', 'Z')
+                .err()
+                .unwrap()
+                .kind,
+            ErrorKind::UnknownToken {
+                what: "node ending",
+                token: "Z".to_string()
+            }
+        );
+        assert_eq!(
+            ghost_map
+                .walk_from_key("AAA".to_string(), "ZZZ".to_string())
+                .err()
+                .unwrap()
+                .kind,
+            ErrorKind::UnknownToken {
+                what: "node",
+                token: "ZZZ".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn walks_that_never_arrive_are_an_error() {
+        let ghost_map: GhostMap = "LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n11A = (11B, 11B)\n11B = (11A, 11A)\n"
+            .parse()
+            .unwrap();
+        for error in [
+            ghost_map.part_one().err().unwrap(),
+            ghost_map.part_two().err().unwrap(),
+        ] {
+            assert!(matches!(error.kind, ErrorKind::Invalid(_)), "{}", error);
+        }
+    }
+}
@@ -1,8 +1,8 @@
 #[cfg(test)]
 mod test_challenge_parsing {
     use advent_of_code_2023::day_9_mirage_maintenance::{parse_report, History};
+    use advent_of_code_2023::inputs::read_input;
     use itertools::Itertools;
-    use std::fs;
 
     #[test]
     fn parse_oasis_line_correctly() {
@@ -20,7 +20,7 @@ mod test_challenge_parsing {
 
     #[test]
     fn parse_oasis_line_correctly_and_do_extrapolation_txt() {
-        let buffer = fs::read_to_string("./data/mirage_maintenance_input_short.txt").unwrap();
+        let buffer = read_input(9, "example").unwrap();
         let histories: Vec<History> = parse_report(&buffer).unwrap();
         let ans_vec = histories
             .iter()
@@ -31,7 +31,7 @@ mod test_challenge_parsing {
 
     #[test]
     fn parse_oasis_multiline_correctly() {
-        let buffer = fs::read_to_string("./data/mirage_maintenance_input_short.txt").unwrap();
+        let buffer = read_input(9, "example").unwrap();
         let histories: Vec<History> = parse_report(&buffer).unwrap();
 
         let vector1 = vec![0, 3, 6, 9, 12, 15];
@@ -45,7 +45,7 @@ mod test_challenge_parsing {
 
     #[test]
     fn parse_oasis_multiline_correctly_and_do_extrapolation_long_txt() {
-        let buffer = fs::read_to_string("./data/mirage_maintenance_input_long.txt").unwrap();
+        let buffer = read_input(9, "input").unwrap();
         let histories: Vec<History> = parse_report(&buffer).unwrap();
         let sum_of_histories: isize = histories.iter().map(|h| h.naive_extrapolate()).sum();
         assert_eq!(sum_of_histories, 1939607039);
@@ -60,7 +60,7 @@ mod test_challenge_parsing {
 
     #[test]
     fn parse_oasis_multiline_correctly_and_do_reverse_extrapolation_long_txt() {
-        let buffer = fs::read_to_string("./data/mirage_maintenance_input_long.txt").unwrap();
+        let buffer = read_input(9, "input").unwrap();
         let histories: Vec<History> = parse_report(&buffer).unwrap();
         let sum_of_histories: isize = histories.iter().map(|h| h.naive_extrapolate_rev()).sum();
         assert_eq!(sum_of_histories, 1041);
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use advent_of_code_2023::day_14_parabolic_reflector_dish::Platform;
+    use advent_of_code_2023::day_2_cube_conundrum::Conundrum;
+    use advent_of_code_2023::day_9_mirage_maintenance::parse_report;
+    use advent_of_code_2023::error::{AocError, ErrorKind};
+    use advent_of_code_2023::inputs::read_input;
+    use advent_of_code_2023::solution::get_day;
+
+    #[test]
+    fn error_display_includes_every_known_location() {
+        let error = AocError::day(
+            5,
+            ErrorKind::WrongTokenCount {
+                what: "map range",
+                expected: 3,
+                found: 2,
+            },
+        )
+        .at_line(37);
+        assert_eq!(
+            error.to_string(),
+            "day 5, line 37: map range has 2 tokens, expected 3"
+        );
+
+        let error = AocError::new(ErrorKind::InvalidChar {
+            what: "platform",
+            character: 'x',
+        })
+        .at_column(4)
+        .at_line(2);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: unexpected character 'x' in platform"
+        );
+    }
+
+    #[test]
+    fn innermost_location_wins() {
+        let error = AocError::day(4, ErrorKind::Empty { what: "card" })
+            .at_line(3)
+            .at_line(10)
+            .in_day(5);
+        assert_eq!(error.day, Some(4));
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.offset_line(20).line, Some(23));
+    }
+
+    #[test]
+    fn almanac_reports_the_line_of_a_short_map_range() {
+        let buffer = read_input(5, "example").unwrap();
+        let mut lines: Vec<&str> = buffer.lines().collect();
+        lines[19] = "37 52";
+        let error = get_day(5)
+            .unwrap()
+            .solve(1, &lines.join("\n"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 5, line 20: map range has 2 tokens, expected 3"
+        );
+    }
+
+    #[test]
+    fn parsers_share_the_same_error_type() {
+        let error = "Game 1: 3 blue, 4 purple"
+            .parse::<Conundrum>()
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.kind,
+            ErrorKind::UnknownToken {
+                what: "cube color",
+                token: "purple".to_string()
+            }
+        );
+        assert_eq!((error.day, error.line), (Some(2), Some(1)));
+
+        let error = "O..#\n.#x.".parse::<Platform>().err().unwrap();
+        assert_eq!(
+            (error.day, error.line, error.column),
+            (Some(14), Some(2), Some(3))
+        );
+
+        let error = parse_report("0 3 6\n1 two 3\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 9, line 2: invalid history value 'two'"
+        );
+    }
+
+    #[test]
+    fn aoc_error_is_a_std_error() {
+        fn solve_day_18(input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(get_day(18).unwrap().solve(1, input)?.to_string())
+        }
+        let error = solve_day_18("R 6 (#70c710)\nX 5 (#0dc571)").err().unwrap();
+        assert_eq!(error.to_string(), "day 18, line 2: unknown direction 'X'");
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use advent_of_code_2023::error::ErrorKind;
+    use advent_of_code_2023::grid::Grid;
+    use itertools::Itertools;
+
+    fn digits(s: &str) -> Grid<u32> {
+        Grid::parse(s, "digits", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn grid_parses_one_row_per_line() {
+        let grid = digits("\n123\n456\n");
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid.get(1, 2), Some(&6));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid[(0, 1)], 2);
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn grid_parse_errors_point_to_the_input() {
+        let error = Grid::parse("12\n\n3x", "digits", |c| c.to_digit(10))
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (Some(3), Some(2)));
+        assert_eq!(
+            error.kind,
+            ErrorKind::InvalidChar {
+                what: "digits",
+                character: 'x'
+            }
+        );
+
+        // Indented rows aren't shifted into place, the whitespace is part of the row
+        let error = Grid::parse("12\n 3", "digits", |c| c.to_digit(10))
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (Some(2), Some(1)));
+
+        let error = Grid::parse("12\n\n345", "digits", |c| c.to_digit(10))
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "line 3: row has 3 cells, expected 2");
+
+        let error = Grid::parse(" \n", "digits", |c| c.to_digit(10))
+            .err()
+            .unwrap();
+        assert_eq!(error.kind, ErrorKind::Empty { what: "digits" });
+    }
+
+    #[test]
+    fn neighbours_stay_inside_the_grid() {
+        let grid = digits("123\n456\n789");
+        let values =
+            |positions: Vec<(usize, usize)>| positions.into_iter().map(|at| grid[at]).collect_vec();
+        assert_eq!(values(grid.neighbours4(0, 0).collect()), vec![2, 4]);
+        assert_eq!(values(grid.neighbours4(1, 1).collect()), vec![2, 4, 6, 8]);
+        assert_eq!(values(grid.neighbours8(0, 2).collect()), vec![2, 5, 6]);
+        assert_eq!(
+            values(grid.neighbours8(1, 1).collect()),
+            vec![1, 2, 3, 4, 6, 7, 8, 9]
+        );
+        assert_eq!(grid.offset((2, 2), (0, 1)), None);
+        assert_eq!(grid.offset((2, 2), (-2, -1)), Some((0, 1)));
+    }
+
+    #[test]
+    fn rows_and_columns_can_be_viewed() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(2).unwrap().collect_vec(), vec![&3, &6]);
+        assert!(grid.column(3).is_none());
+        let column_sums = grid
+            .columns()
+            .map(|column| column.sum::<u32>())
+            .collect_vec();
+        assert_eq!(column_sums, vec![5, 7, 9]);
+        assert_eq!(grid.positions().last(), Some((1, 2)));
+        assert_eq!(grid.iter().find(|(_, &n)| n == 5).unwrap().0, (1, 1));
+    }
+
+    #[test]
+    fn grid_can_be_transposed_rotated_and_flipped() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.transposed().to_string(), "14\n25\n36\n");
+        assert_eq!(grid.rotated_clockwise().to_string(), "41\n52\n63\n");
+        assert_eq!(grid.rotated_counterclockwise().to_string(), "36\n25\n14\n");
+        assert_eq!(grid.flipped_horizontally().to_string(), "321\n654\n");
+        assert_eq!(grid.flipped_vertically().to_string(), "456\n123\n");
+        assert_eq!(
+            grid.rotated_clockwise().rotated_counterclockwise(),
+            grid.clone()
+        );
+    }
+
+    #[test]
+    fn grid_renders_with_a_cell_printer() {
+        let mut grid = Grid::new(2, 3, false);
+        grid[(0, 1)] = true;
+        *grid.get_mut(1, 2).unwrap() = true;
+        assert!(grid.get_mut(2, 0).is_none());
+        assert_eq!(grid.render(|&on| if on { '#' } else { '.' }), ".#.\n..#\n");
+        let sums = Grid::from_fn(2, 2, |row_n, col_n| row_n + col_n);
+        assert_eq!(sums.map(|n| n * 10).to_string(), "010\n1020\n");
+    }
+}
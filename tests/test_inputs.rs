@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use advent_of_code_2023::error::ErrorKind;
+    use advent_of_code_2023::inputs::{
+        for_each_normalized_line, normalize, read_input, Charset, InputStore, EXAMPLE, PUZZLE_INPUT,
+    };
+    use advent_of_code_2023::solution::{get_day, DAYS};
+    use std::io::Cursor;
+    use std::path::{Path, PathBuf};
+
+    // The data directory of the crate, wherever the tests are run from
+    fn data_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("data")
+    }
+
+    #[test]
+    fn inputs_are_found_by_day_and_variant() {
+        let store = InputStore::new(data_dir());
+        assert_eq!(
+            store.path(10, "area_loop_hard"),
+            data_dir().join("day_10").join("area_loop_hard.txt")
+        );
+        assert_eq!(
+            store.read(6, EXAMPLE).unwrap().lines().next(),
+            Some(" Time:      7  15   30")
+        );
+        assert_eq!(
+            store.variants(8),
+            vec!["example", "ghost_example", "input", "repeating_example"]
+        );
+        assert!(store.variants(25).is_empty());
+    }
+
+    #[test]
+    fn every_registered_day_has_its_puzzle_input() {
+        for day in DAYS.iter() {
+            assert!(
+                read_input(day.number, PUZZLE_INPUT).is_ok(),
+                "day {} has no puzzle input",
+                day.number
+            );
+        }
+    }
+
+    #[test]
+    fn missing_inputs_are_errors() {
+        let error = InputStore::new(data_dir())
+            .read(10, "area_loop_impossible")
+            .err()
+            .unwrap();
+        assert_eq!(error.day, Some(10));
+        assert_eq!(
+            error.kind,
+            ErrorKind::MissingInput {
+                variant: "area_loop_impossible".to_string(),
+                path: data_dir()
+                    .join("day_10")
+                    .join("area_loop_impossible.txt")
+                    .display()
+                    .to_string()
+            }
+        );
+    }
+
+    // The same input as saved by an editor that likes BOMs, CRLF and trailing tabs
+    fn messy(input: &str) -> String {
+        let lines: Vec<String> = input.lines().map(|line| format!("{} \t", line)).collect();
+        format!("\u{feff}{}\r\n\r\n  \r\n", lines.join("\r\n"))
+    }
+
+    #[test]
+    fn normalizing_fixes_line_endings_bom_tabs_and_trailing_whitespace() {
+        let normalized = normalize("\u{feff}a\tb  \r\nc\rd\n\n e\t\r\n\r\n\n", Charset::Ascii);
+        assert_eq!(normalized.unwrap(), "a b\nc\nd\n\n e\n");
+        assert_eq!(normalize(" \n\t\r\n", Charset::Ascii).unwrap(), "");
+        assert_eq!(normalize("x", Charset::Ascii).unwrap(), "x\n");
+    }
+
+    #[test]
+    fn lines_are_normalized_one_at_a_time_like_all_at_once() {
+        let inputs = [
+            "\u{feff}a\tb  \r\nc\rd\n\n e\t\r\n\r\n\n",
+            " \n\t\r\n",
+            "x",
+            "\r\r\nx\r",
+            "\n\nx\n\ny\n\n",
+        ];
+        for input in inputs {
+            let mut lines = vec![];
+            for_each_normalized_line(1, Cursor::new(input), Charset::Ascii, |line_n, line| {
+                lines.push((line_n, line.to_string()));
+                Ok(())
+            })
+            .unwrap();
+            let normalized = normalize(input, Charset::Ascii).unwrap();
+            let expected: Vec<(usize, String)> =
+                (1..).zip(normalized.lines().map(str::to_string)).collect();
+            assert_eq!(lines, expected, "{:?}", input);
+        }
+        let error = for_each_normalized_line(
+            7,
+            Cursor::new("32T3K 765\rT55J5 6é4"),
+            Charset::Ascii,
+            |_, _| Ok(()),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 7, line 2, column 8: unexpected character 'é' in input"
+        );
+    }
+
+    #[test]
+    fn ascii_days_reject_anything_else() {
+        let error = normalize("32T3K 765\nT55J5 6é4", Charset::Ascii)
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (Some(2), Some(8)));
+        assert_eq!(
+            error.kind,
+            ErrorKind::InvalidChar {
+                what: "input",
+                character: 'é'
+            }
+        );
+        assert!(normalize("dos€tres", Charset::Unicode).is_ok());
+        let answer = get_day(1).unwrap().solve(2, "\u{feff}é1€two\r\n").unwrap();
+        assert_eq!(answer.to_string(), "12");
+
+        let error = get_day(7)
+            .unwrap()
+            .parse("32T3K 765\r\nT55J5 6é4")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 7, line 2, column 8: unexpected character 'é' in input"
+        );
+    }
+
+    #[test]
+    fn every_day_solves_messy_inputs_like_clean_ones() {
+        let samples = [
+            (1, "spelled_out"),
+            (2, EXAMPLE),
+            (3, EXAMPLE),
+            (4, EXAMPLE),
+            (5, EXAMPLE),
+            (6, EXAMPLE),
+            (7, EXAMPLE),
+            (8, "ghost_example"),
+            (9, EXAMPLE),
+            (10, "area_loop_easy"),
+            (11, EXAMPLE),
+            (12, EXAMPLE),
+            (13, EXAMPLE),
+            (14, EXAMPLE),
+            (15, PUZZLE_INPUT),
+            (18, EXAMPLE),
+        ];
+        assert_eq!(samples.len(), DAYS.len());
+        for (number, variant) in samples {
+            let day = get_day(number).unwrap();
+            let input = read_input(number, variant).unwrap();
+            for part in [1, 2] {
+                let solve = |input: &str| match day.solve(part, input) {
+                    Ok(answer) => answer.to_string(),
+                    Err(e) => e.to_string(),
+                };
+                assert_eq!(
+                    solve(&messy(&input)),
+                    solve(&input),
+                    "day {} part {}",
+                    number,
+                    part
+                );
+            }
+        }
+    }
+}
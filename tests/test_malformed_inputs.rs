@@ -0,0 +1,191 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::XorShift;
+    use advent_of_code_2023::day_10_pipe_maze::PipeMaze;
+    use advent_of_code_2023::day_13_point_of_incidence::summarize_reflections;
+    use advent_of_code_2023::day_3_gear_ratios::Schematic;
+    use advent_of_code_2023::day_8_haunted_wasteland::GhostMap;
+    use advent_of_code_2023::error::ErrorKind;
+    use advent_of_code_2023::inputs::read_input;
+    use advent_of_code_2023::solution::{get_day, DAYS};
+    use std::panic;
+
+    const SAMPLES: [(usize, &str); 16] = [
+        (1, "example"),
+        (2, "example"),
+        (3, "example"),
+        (4, "example"),
+        (5, "example"),
+        (6, "example"),
+        (7, "example"),
+        (8, "ghost_example"),
+        (9, "example"),
+        (10, "area_loop_easy"),
+        (11, "example"),
+        (12, "example"),
+        (13, "example"),
+        (14, "example"),
+        (15, "input"),
+        (18, "example"),
+    ];
+    const MUTATIONS_PER_SAMPLE: u64 = 300;
+
+    // Seeded, so every run mutates the samples in the same way
+    struct Mutator(XorShift);
+
+    impl Mutator {
+        fn below(&mut self, n: usize) -> usize {
+            self.0.below(n as u64) as usize
+        }
+
+        fn mutate(&mut self, input: &str) -> String {
+            const ALPHABET: [&str; 16] = [
+                "",
+                " ",
+                "\n",
+                "\n\n",
+                ":",
+                ",",
+                ";",
+                "|",
+                "=",
+                "(",
+                "#",
+                "S",
+                "-",
+                "é",
+                "99999999999999999999999",
+                "-to-",
+            ];
+            let mut chars: Vec<char> = input.chars().collect();
+            let mut lines: Vec<&str> = input.lines().collect();
+            match self.below(6) {
+                0 => {
+                    let at = self.below(chars.len());
+                    chars.truncate(at);
+                }
+                1 => {
+                    let at = self.below(chars.len());
+                    let len = 1 + self.below(8);
+                    chars.drain(at..(at + len).min(chars.len()));
+                }
+                2 => {
+                    let at = self.below(chars.len() + 1);
+                    let token = ALPHABET[self.below(ALPHABET.len())];
+                    chars.splice(at..at, token.chars());
+                }
+                3 => {
+                    let at = self.below(chars.len());
+                    if let Some(c) = chars.get_mut(at) {
+                        *c = ALPHABET[self.below(ALPHABET.len())]
+                            .chars()
+                            .next()
+                            .unwrap_or('x');
+                    }
+                }
+                4 => {
+                    let at = self.below(lines.len());
+                    if at < lines.len() {
+                        lines.remove(at);
+                    }
+                    return lines.join("\n");
+                }
+                _ => {
+                    let (a, b) = (self.below(lines.len()), self.below(lines.len()));
+                    if b < lines.len() {
+                        lines.insert(a, lines[b]);
+                    }
+                    return lines.join("\n");
+                }
+            }
+            chars.into_iter().collect()
+        }
+    }
+
+    // The solvers get every mutated input that parses, since a parser accepting it promises they can take it
+    #[test]
+    fn every_parser_and_solver_survives_mutated_samples() {
+        assert_eq!(SAMPLES.len(), DAYS.len());
+        let mut panics = vec![];
+        for (day, variant) in SAMPLES {
+            let buffer = read_input(day, variant).unwrap();
+            let mut mutator = Mutator(XorShift::new(0x9E37_79B9_7F4A_7C15 ^ day as u64));
+            for round in 0..MUTATIONS_PER_SAMPLE {
+                let mut mutated = mutator.mutate(&buffer);
+                // Every few rounds, stack a couple more mutations on top
+                for _ in 0..(round % 3) {
+                    mutated = mutator.mutate(&mutated);
+                }
+                let solved = panic::catch_unwind(|| {
+                    if let Ok(solution) = get_day(day).unwrap().parse(&mutated) {
+                        let _ = solution.part_one();
+                        let _ = solution.part_two();
+                    }
+                });
+                if solved.is_err() {
+                    panics.push(format!("day {} round {}: {:?}", day, round, mutated));
+                }
+            }
+        }
+        assert!(
+            panics.is_empty(),
+            "parsers or solvers panicked on:\n{}",
+            panics.join("\n")
+        );
+    }
+
+    #[test]
+    fn degenerate_inputs_are_errors_not_panics() {
+        for day in DAYS.iter() {
+            for input in ["", "\n\n", "\u{feff}", "🎄"] {
+                let parsed = panic::catch_unwind(|| day.parse(input).is_ok());
+                assert!(parsed.is_ok(), "day {} panicked on {:?}", day.number, input);
+            }
+        }
+    }
+
+    #[test]
+    fn pipe_maze_start_that_cannot_be_inferred_is_an_error() {
+        let error = ".....\n.S-7.\n.|.|.\n.L-J.\n...S."
+            .parse::<PipeMaze>()
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (Some(5), Some(4)));
+        let error = ".....\n.S...\n.....".parse::<PipeMaze>().err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 10, line 2, column 2: The start is not connected to exactly two pipes"
+        );
+    }
+
+    #[test]
+    fn ghost_map_nodes_must_lead_to_known_nodes() {
+        let error = "LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, CCC)\nZZZ = (ZZZ, ZZZ)"
+            .parse::<GhostMap>()
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.kind,
+            ErrorKind::UnknownToken {
+                what: "node",
+                token: "CCC".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn empty_schematics_and_bad_mirror_notes_are_errors() {
+        let error = "".parse::<Schematic>().err().unwrap();
+        assert_eq!(error.kind, ErrorKind::Empty { what: "schematic" });
+
+        let error = summarize_reflections("#.#\n.#.\n\n#.#\n.x.", 0)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 13, line 5, column 2: unexpected character 'x' in pattern"
+        );
+    }
+}
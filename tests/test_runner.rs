@@ -0,0 +1,240 @@
+#[cfg(test)]
+mod tests {
+    use advent_of_code_2023::inputs::{InputStore, EXAMPLE};
+    use std::io::Write;
+    use std::process::{Command, Output, Stdio};
+
+    fn aoc2023(args: &[&str], stdin: &str) -> Output {
+        aoc2023_logging(args, stdin, "")
+    }
+
+    fn aoc2023_logging(args: &[&str], stdin: &str, rust_log: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_aoc2023"))
+            .args(args)
+            .env("RUST_LOG", rust_log)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    #[test]
+    fn run_reads_the_input_file_and_prints_the_answer() {
+        let cards = InputStore::from_env().path(4, EXAMPLE);
+        let output = aoc2023(
+            &[
+                "run",
+                "--day",
+                "4",
+                "--part",
+                "2",
+                "--input",
+                cards.to_str().unwrap(),
+            ],
+            "",
+        );
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "30\n");
+    }
+
+    #[test]
+    fn run_reads_stdin_when_there_is_no_input_file() {
+        let output = aoc2023(
+            &["run", "--day", "6", "--part", "1"],
+            "Time:      7  15   30\nDistance:  9  40  200\n",
+        );
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "288\n");
+    }
+
+    #[test]
+    fn run_kerns_the_document_for_day_6_part_2() {
+        let output = aoc2023(
+            &["run", "-d", "6", "-p", "2", "-i", "-"],
+            "Time:      7  15   30\nDistance:  9  40  200\n",
+        );
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "71503\n");
+    }
+
+    #[test]
+    fn run_fails_for_parts_and_days_that_are_not_implemented() {
+        let output = aoc2023(&["run", "--day", "7", "--part", "1"], "");
+        assert!(!output.status.success());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("day 7: part 1 is not implemented"));
+
+        let output = aoc2023(&["run", "--day", "16", "--part", "1"], "");
+        assert!(!output.status.success());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("Day 16 is not implemented"));
+    }
+
+    #[test]
+    fn run_fails_on_malformed_input() {
+        let output = aoc2023(
+            &["run", "--day", "4", "--part", "1"],
+            "Card 1 41 48 | 83 86",
+        );
+        assert!(!output.status.success());
+        assert!(output.stdout.is_empty());
+    }
+
+    #[test]
+    fn run_is_quiet_unless_logging_is_asked_for() {
+        let almanac = InputStore::from_env().path(5, EXAMPLE);
+        let almanac = almanac.to_str().unwrap();
+        let output = aoc2023(&["run", "-d", "5", "-p", "2", "-i", almanac], "");
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "46\n");
+        assert!(output.stderr.is_empty());
+
+        let output = aoc2023_logging(
+            &["run", "-d", "5", "-p", "2", "-i", almanac],
+            "",
+            "advent_of_code_2023::day_5_fertilizer=trace",
+        );
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "46\n");
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("Intersection found!"));
+        assert!(stderr.lines().all(|line| line.contains("day_5_fertilizer")));
+    }
+
+    #[test]
+    fn bench_prints_a_timing_table() {
+        let output = aoc2023(&["bench", "--day", "6", "--runs", "3"], "");
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Day  Title"));
+        assert!(lines[1].starts_with("  6  Wait For It"));
+        assert!(lines[2].trim_start().starts_with("Total"));
+
+        let output = aoc2023(&["bench", "--day", "6", "--data", "./nowhere"], "");
+        assert!(!output.status.success());
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "error: day 6: no 'input' input, ./nowhere/day_06/input.txt can't be read\n"
+        );
+    }
+
+    #[test]
+    fn check_reports_every_answer_of_the_manifest() {
+        let output = aoc2023(&["check", "--day", "6"], "");
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.starts_with("PASS    day 6 part 1 (example)\n"));
+        assert!(stdout.ends_with("\n6 passed, 0 failed, 0 missing\n"));
+
+        let manifest = std::env::temp_dir().join("aoc2023_wrong_answers.txt");
+        std::fs::write(&manifest, "6 1 example 289\n").unwrap();
+        let output = aoc2023(&["check", "--answers", manifest.to_str().unwrap()], "");
+        assert!(!output.status.success());
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "FAIL    day 6 part 1 (example): expected 289, got 288\n"
+        );
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "error: 0 passed, 1 failed, 0 missing\n"
+        );
+    }
+
+    #[test]
+    fn run_reads_variants_from_the_data_directory() {
+        let output = aoc2023(&["run", "-d", "4", "-p", "2", "--variant", "example"], "");
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "30\n");
+
+        let data = std::env::temp_dir().join("aoc2023_data");
+        std::fs::create_dir_all(data.join("day_06")).unwrap();
+        std::fs::write(data.join("day_06/input.txt"), "Time: 7\nDistance: 9\n").unwrap();
+        let output = Command::new(env!("CARGO_BIN_EXE_aoc2023"))
+            .args(["run", "-d", "6", "-p", "1", "-v", "input"])
+            .env("AOC_DATA_DIR", &data)
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "4\n");
+
+        let output = aoc2023(&["run", "-d", "6", "-p", "1", "-v", "x", "-i", "-"], "");
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "error: Only one of --input and --variant can be given\n"
+        );
+    }
+
+    #[test]
+    fn run_prints_a_calibration_report_for_day_1() {
+        let output = aoc2023(
+            &["run", "-d", "1", "-p", "1", "--report", "table"],
+            "a1b\nxyz\r\n",
+        );
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("\n    1  1      digit  1..2"));
+        assert!(stdout.ends_with("Sum: 11\nLines without digits: 2\n"));
+
+        let output = aoc2023(
+            &[
+                "run",
+                "-d",
+                "1",
+                "-p",
+                "2",
+                "-v",
+                "spelled_out",
+                "--report",
+                "json",
+            ],
+            "",
+        );
+        assert!(output.status.success());
+        assert!(String::from_utf8(output.stdout)
+            .unwrap()
+            .starts_with("{\n  \"sum\": 281,\n"));
+
+        let output = aoc2023(&["run", "-d", "6", "-p", "1", "--report", "json"], "");
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "error: --report is only available for day 1\n"
+        );
+        let output = aoc2023(&["run", "-d", "1", "-p", "1", "--report", "xml"], "");
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "error: The value of --report must be table or json, got 'xml'\n"
+        );
+    }
+
+    #[test]
+    fn generate_makes_up_inputs_that_solve() {
+        let output = aoc2023(
+            &["generate", "--day", "2", "--games", "20", "--seed", "5"],
+            "",
+        );
+        assert!(output.status.success());
+        let log = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(log.lines().count(), 20);
+        assert!(log.starts_with("Game 1: "));
+        let again = aoc2023(&["generate", "-d", "2", "-g", "20", "-s", "5"], "");
+        assert_eq!(String::from_utf8(again.stdout).unwrap(), log);
+
+        let output = aoc2023(&["run", "-d", "2", "-p", "1"], &log);
+        assert!(output.status.success());
+
+        let output = aoc2023(&["generate", "--day", "3"], "");
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "error: Day 3 can't be generated\n"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use advent_of_code_2023::day_2_cube_conundrum::Conundrum;
+    use advent_of_code_2023::inputs::read_input;
+    use advent_of_code_2023::solution::{get_day, Solution, DAYS};
+    use itertools::Itertools;
+
+    #[test]
+    fn registry_is_sorted_by_day_without_repeats() {
+        let numbers = DAYS.iter().map(|day| day.number).collect_vec();
+        assert!(numbers.iter().tuple_windows().all(|(a, b)| a < b));
+        assert_eq!(get_day(14).unwrap().title, "Parabolic Reflector Dish");
+        assert!(get_day(16).is_none());
+    }
+
+    #[test]
+    fn solution_can_be_used_directly_on_the_day_type() {
+        let buffer = read_input(2, "example").unwrap();
+        let conundrum = <Conundrum as Solution>::parse(&buffer).unwrap();
+        assert_eq!(conundrum.part_one().unwrap().to_string(), "8");
+        assert_eq!(conundrum.part_two().unwrap().to_string(), "2286");
+    }
+
+    #[test]
+    fn every_day_in_the_registry_solves_its_sample() {
+        let samples = [
+            (1, 2, "example", "142"),
+            (2, 1, "example", "8"),
+            (3, 2, "example", "467835"),
+            (4, 2, "example", "30"),
+            (5, 1, "example", "35"),
+            (6, 2, "example", "71503"),
+            (7, 2, "example", "5905"),
+            (8, 1, "example", "2"),
+            (9, 2, "example", "2"),
+            (10, 2, "area_loop_easy", "4"),
+            (11, 1, "example", "374"),
+            (12, 2, "example", "525152"),
+            (13, 1, "example", "405"),
+            (14, 1, "example", "136"),
+            (15, 1, "input", "508498"),
+            (18, 1, "example", "62"),
+        ];
+        assert_eq!(samples.len(), DAYS.len());
+        for (day, part, variant, expected) in samples {
+            let buffer = read_input(day, variant).unwrap();
+            let answer = get_day(day).unwrap().solve(part, &buffer).unwrap();
+            assert_eq!(answer.to_string(), expected, "day {} part {}", day, part);
+        }
+    }
+
+    #[test]
+    fn missing_parts_are_reported_with_the_day() {
+        let buffer = read_input(15, "input").unwrap();
+        let error = get_day(15).unwrap().solve(2, &buffer).err().unwrap();
+        assert_eq!(error.to_string(), "day 15: part 2 is not implemented");
+    }
+}
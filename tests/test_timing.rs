@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use advent_of_code_2023::inputs::{read_input, PUZZLE_INPUT};
+    use advent_of_code_2023::solution::get_day;
+    use advent_of_code_2023::timing::{time_day, timing_table, DayTiming};
+    use std::time::Duration;
+
+    #[test]
+    fn parts_that_are_not_implemented_are_not_timed() {
+        let day = get_day(15).unwrap();
+        let buffer = read_input(15, PUZZLE_INPUT).unwrap();
+        let timing = time_day(day, &buffer, 3).unwrap();
+        assert_eq!((timing.day, timing.title), (15, "Lens Library"));
+        assert!(timing.part_one.is_some());
+        assert!(timing.part_two.is_none());
+        assert_eq!(timing.total(), timing.parse + timing.part_one.unwrap());
+    }
+
+    #[test]
+    fn timing_fails_on_malformed_input() {
+        let error = time_day(get_day(9).unwrap(), "1 2 3\n4 five 6", 1)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 9, line 2: invalid history value 'five'"
+        );
+    }
+
+    #[test]
+    fn timing_table_has_a_row_per_day_and_a_total() {
+        let timing = |day, part_two| DayTiming {
+            day,
+            title: "Some Day",
+            parse: Duration::from_micros(250),
+            part_one: Some(Duration::from_millis(2)),
+            part_two,
+        };
+        let table = timing_table(&[timing(1, None), timing(12, Some(Duration::from_secs(1)))]);
+        let rows: Vec<Vec<&str>> = table
+            .lines()
+            .map(|line| {
+                line.split("  ")
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["Day", "Title", "Parse", "Part 1", "Part 2", "Total"],
+                vec!["1", "Some Day", "250.000µs", "2.000ms", "-", "2.250ms"],
+                vec!["12", "Some Day", "250.000µs", "2.000ms", "1.000s", "1.002s"],
+                vec!["Total", "500.000µs", "4.000ms", "1.000s", "1.004s"],
+            ]
+        );
+    }
+}